@@ -0,0 +1,46 @@
+// Helpers shared between the materials, import with
+// #import "shaders/common.wgsl"
+
+// a number in 0..1 that jumps around as p moves, the same every time for the same p
+fn hash3(p: vec3<f32>) -> f32 {
+    let q = fract(p * 0.3183099 + vec3<f32>(0.1, 0.2, 0.3)) * 17.0;
+    return fract(q.x * q.y * q.z * (q.x + q.y + q.z));
+}
+
+// smooth noise in 0..1 with one random value per unit cube
+fn value_noise(p: vec3<f32>) -> f32 {
+    let i = floor(p);
+    let f = fract(p);
+    let u = f * f * (3.0 - 2.0 * f);
+
+    let bottom = mix(
+        mix(hash3(i + vec3<f32>(0.0, 0.0, 0.0)), hash3(i + vec3<f32>(1.0, 0.0, 0.0)), u.x),
+        mix(hash3(i + vec3<f32>(0.0, 1.0, 0.0)), hash3(i + vec3<f32>(1.0, 1.0, 0.0)), u.x),
+        u.y
+    );
+    let top = mix(
+        mix(hash3(i + vec3<f32>(0.0, 0.0, 1.0)), hash3(i + vec3<f32>(1.0, 0.0, 1.0)), u.x),
+        mix(hash3(i + vec3<f32>(0.0, 1.0, 1.0)), hash3(i + vec3<f32>(1.0, 1.0, 1.0)), u.x),
+        u.y
+    );
+    return mix(bottom, top, u.z);
+}
+
+// four octaves of value noise, still in 0..1
+fn fbm(p: vec3<f32>) -> f32 {
+    var total: f32 = 0.0;
+    var amplitude: f32 = 0.5;
+    var q = p;
+    for (var octave: i32 = 0; octave < 4; octave = octave + 1) {
+        total = total + value_noise(q) * amplitude;
+        q = q * 2.0;
+        amplitude = amplitude * 0.5;
+    }
+    return total / 0.9375;
+}
+
+// a light over the right shoulder with some ambient, enough to see the shape without bevy's lights
+fn simple_diffuse(normal: vec3<f32>) -> f32 {
+    let light = normalize(vec3<f32>(0.5, 1.0, 0.3));
+    return max(dot(normalize(normal), light), 0.0) * 0.7 + 0.3;
+}
@@ -0,0 +1,72 @@
+#import bevy_pbr::mesh_view_bindings
+#import bevy_pbr::mesh_bindings
+#import bevy_pbr::mesh_functions
+
+#import "shaders/common.wgsl"
+
+struct DissolveMaterial {
+    color: vec4<f32>,
+    edge_color: vec4<f32>,
+    progress: f32,
+    edge_width: f32,
+    noise_scale: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> material: DissolveMaterial;
+@group(1) @binding(1)
+var noise_texture: texture_2d<f32>;
+@group(1) @binding(2)
+var noise_sampler: sampler;
+
+struct Vertex {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+#ifdef NOISE_TEXTURE
+    @location(2) uv: vec2<f32>,
+#endif
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) world_normal: vec3<f32>,
+    // the noise sticks to the mesh, so it's sampled in mesh space
+    @location(1) local_position: vec3<f32>,
+#ifdef NOISE_TEXTURE
+    @location(2) uv: vec2<f32>,
+#endif
+};
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+    let world_position = mesh_position_local_to_world(mesh.model, vec4<f32>(vertex.position, 1.0));
+    out.clip_position = mesh_position_world_to_clip(world_position);
+    out.world_normal = mesh_normal_local_to_world(vertex.normal);
+    out.local_position = vertex.position;
+#ifdef NOISE_TEXTURE
+    out.uv = vertex.uv;
+#endif
+    return out;
+}
+
+@fragment
+fn fragment(input: VertexOutput) -> @location(0) vec4<f32> {
+#ifdef NOISE_TEXTURE
+    let noise = textureSample(noise_texture, noise_sampler, input.uv).r;
+#else
+    let noise = fbm(input.local_position * material.noise_scale);
+#endif
+
+    // starts below 0 so nothing glows at progress 0, and ends at 1 where all the noise is gone
+    let cutoff = mix(-material.edge_width, 1.0, material.progress);
+    if (noise < cutoff) {
+        discard;
+    }
+    // 1 right at the burning edge, 0 edge_width further in
+    let edge = 1.0 - smoothstep(0.0, material.edge_width, noise - cutoff);
+
+    let diffuse = simple_diffuse(input.world_normal);
+    let color = mix(material.color.rgb * diffuse, material.edge_color.rgb, edge);
+    return vec4<f32>(color, 1.0);
+}
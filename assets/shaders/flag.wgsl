@@ -0,0 +1,98 @@
+#import bevy_pbr::mesh_view_bindings
+#import bevy_pbr::mesh_bindings
+#import bevy_pbr::mesh_functions
+
+#import "shaders/common.wgsl"
+
+struct FlagMaterial {
+    color: vec4<f32>,
+    wind_direction: vec3<f32>,
+    wind_speed: f32,
+    gust_strength: f32,
+    gust_frequency: f32,
+    wave_length: f32,
+    amplitude: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> material: FlagMaterial;
+
+struct Vertex {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) world_normal: vec3<f32>,
+};
+
+let TAU = 6.283185307179586;
+
+// 1 in full wind, down to 1 - gust_strength in the lulls between gusts
+fn gust() -> f32 {
+    let t = globals.time * material.gust_frequency * TAU;
+    // two sines out of step, so the gusts don't come in a steady rhythm
+    let lull = (sin(t) * 0.5 + 0.5) * (sin(t * 2.3 + 1.0) * 0.5 + 0.5);
+    return 1.0 - material.gust_strength * lull;
+}
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    // the plane lies in its local XZ with the normal up Y, so bring the wind into that space,
+    // the inverse of the model is the transpose of its inverse transpose
+    let world_to_local = transpose(mat3x3<f32>(
+        mesh.inverse_transpose_model[0].xyz,
+        mesh.inverse_transpose_model[1].xyz,
+        mesh.inverse_transpose_model[2].xyz
+    ));
+    let wind = world_to_local * material.wind_direction;
+
+    // the part of the wind along the cloth sets which way the waves run,
+    // the part through it leans the whole flag towards the side it blows to
+    var along = vec2<f32>(1.0, 0.0);
+    var lean = 0.0;
+    if (dot(wind, wind) > 0.0) {
+        let direction = normalize(wind);
+        lean = direction.y;
+        if (dot(direction.xz, direction.xz) > 0.0001) {
+            along = normalize(direction.xz);
+        }
+    }
+    let across = vec2<f32>(-along.y, along.x);
+    // the position measured along and across the wind
+    let p = vec2<f32>(dot(vertex.position.xz, along), dot(vertex.position.xz, across));
+
+    let k = TAU / max(material.wave_length, 0.001);
+    let travel = globals.time * material.wind_speed * k;
+    let phase_a = k * (p.x + 0.5 * p.y) - travel;
+    let phase_b = k * (1.7 * p.x - 0.8 * p.y) - 1.3 * travel;
+
+    // 0 at the pinned edge, all the way at the free one
+    let weight = vertex.uv.x * gust();
+    let height = material.amplitude * (0.7 * sin(phase_a) + 0.3 * sin(phase_b) + lean) * weight;
+
+    // the slope of the waves for the normal, the slope of the weight itself is small next to it and left out
+    let slope_along = material.amplitude * k * (0.7 * cos(phase_a) + 0.3 * 1.7 * cos(phase_b)) * weight;
+    let slope_across = material.amplitude * k * (0.7 * 0.5 * cos(phase_a) - 0.3 * 0.8 * cos(phase_b)) * weight;
+    let slope = along * slope_along + across * slope_across;
+    let normal = normalize(vertex.normal - vec3<f32>(slope.x, 0.0, slope.y));
+
+    var out: VertexOutput;
+    let position = vertex.position + vertex.normal * height;
+    let world_position = mesh_position_local_to_world(mesh.model, vec4<f32>(position, 1.0));
+    out.clip_position = mesh_position_world_to_clip(world_position);
+    out.world_normal = mesh_normal_local_to_world(normal);
+    return out;
+}
+
+@fragment
+fn fragment(@builtin(front_facing) is_front: bool, input: VertexOutput) -> @location(0) vec4<f32> {
+    // both sides of the cloth show, the back one faces the other way
+    var normal = normalize(input.world_normal);
+    if (!is_front) {
+        normal = -normal;
+    }
+    return vec4<f32>(material.color.rgb * simple_diffuse(normal), material.color.a);
+}
@@ -0,0 +1,81 @@
+#import bevy_pbr::mesh_view_bindings
+#import bevy_pbr::mesh_bindings
+#import bevy_pbr::mesh_functions
+
+#import "shaders/common.wgsl"
+
+struct FlowMaterial {
+    color: vec4<f32>,
+    scroll: vec2<f32>,
+    layer_scroll: vec2<f32>,
+    tiling: f32,
+    flow_strength: f32,
+    flow_speed: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> material: FlowMaterial;
+@group(1) @binding(1)
+var color_texture: texture_2d<f32>;
+@group(1) @binding(2)
+var color_sampler: sampler;
+@group(1) @binding(3)
+var flow_texture: texture_2d<f32>;
+@group(1) @binding(4)
+var flow_sampler: sampler;
+
+struct Vertex {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) world_normal: vec3<f32>,
+    @location(1) uv: vec2<f32>,
+};
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+    let world_position = mesh_position_local_to_world(mesh.model, vec4<f32>(vertex.position, 1.0));
+    out.clip_position = mesh_position_world_to_clip(world_position);
+    out.world_normal = mesh_normal_local_to_world(vertex.normal);
+    out.uv = vertex.uv;
+    return out;
+}
+
+// the texture at uv, pushed along the flow map read at the mesh uv
+fn flow_sample(uv: vec2<f32>, mesh_uv: vec2<f32>) -> vec4<f32> {
+#ifdef FLOW_MAP
+    let flow = (textureSample(flow_texture, flow_sampler, mesh_uv).rg * 2.0 - 1.0) * material.flow_strength;
+
+    // the uvs can't be pushed forever or the texture smears, so two copies half a cycle
+    // apart jump back in turn, each one hidden by the other while it does
+    let phase = fract(globals.time * material.flow_speed);
+    let other_phase = fract(globals.time * material.flow_speed + 0.5);
+    let a = textureSample(color_texture, color_sampler, uv - flow * phase);
+    // shifted so the two copies don't line up
+    let b = textureSample(color_texture, color_sampler, uv - flow * other_phase + vec2<f32>(0.5));
+    // all b when a jumps back at phase 0, all a when b does at phase 0.5
+    let weight = abs(1.0 - 2.0 * phase);
+    return mix(a, b, weight);
+#else
+    return textureSample(color_texture, color_sampler, uv);
+#endif
+}
+
+@fragment
+fn fragment(input: VertexOutput) -> @location(0) vec4<f32> {
+    let uv = input.uv * material.tiling;
+    var color = flow_sample(uv + material.scroll * globals.time, input.uv);
+#ifdef TWO_LAYERS
+    // scaled and offset as well, so the layers never line up again
+    let layer_uv = uv * 0.77 + vec2<f32>(0.31, 0.17) + material.layer_scroll * globals.time;
+    color = mix(color, flow_sample(layer_uv, input.uv), 0.5);
+#endif
+
+    color = color * material.color;
+    return vec4<f32>(color.rgb * simple_diffuse(input.world_normal), color.a);
+}
@@ -0,0 +1,28 @@
+#version 450
+// GLSL version of geo_frag.wgsl
+
+layout(location = 0) in vec4 v_WorldPosition;
+layout(location = 1) in vec3 v_WorldNormal;
+layout(location = 2) in vec2 v_Uv;
+
+layout(location = 0) out vec4 o_Target;
+
+layout(set = 0, binding = 9) uniform Globals {
+    float time;
+    float delta_time;
+    uint frame_count;
+};
+
+const float TAU = 6.283185307179586;
+
+vec4 get_wave(vec2 uv) {
+    vec2 centered_uv = uv * 2.0 - 1.0;
+    float radial_dist = length(centered_uv);
+    float wave = cos((radial_dist - time * 0.1) * TAU * 3.0);
+    wave *= 1.0 - radial_dist;
+    return vec4(wave);
+}
+
+void main() {
+    o_Target = get_wave(v_Uv);
+}
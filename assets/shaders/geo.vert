@@ -0,0 +1,44 @@
+#version 450
+// GLSL version of geo_vert.wgsl
+
+layout(location = 0) in vec3 Vertex_Position;
+layout(location = 1) in vec3 Vertex_Normal;
+layout(location = 2) in vec2 Vertex_Uv;
+
+layout(location = 0) out vec4 v_WorldPosition;
+layout(location = 1) out vec3 v_WorldNormal;
+layout(location = 2) out vec2 v_Uv;
+
+// the start of bevy_pbr::mesh_view_types View, only what we use
+layout(set = 0, binding = 0) uniform View {
+    mat4 ViewProj;
+};
+
+// globals.time in WGSL
+layout(set = 0, binding = 9) uniform Globals {
+    float time;
+    float delta_time;
+    uint frame_count;
+};
+
+layout(set = 2, binding = 0) uniform Mesh {
+    mat4 Model;
+    mat4 InverseTransposeModel;
+    uint flags;
+};
+
+const float TAU = 6.283185307179586;
+
+void main() {
+    float wave = cos((Vertex_Uv.y - time * 0.1) * TAU * 3.0);
+    float wave2 = cos((Vertex_Uv.x - time * 0.1) * TAU * 3.0);
+
+    float amplitude = 0.1;
+    vec3 position = Vertex_Position;
+    position.y = wave * wave2 * amplitude;
+
+    v_Uv = Vertex_Uv;
+    v_WorldPosition = Model * vec4(position, 1.0);
+    v_WorldNormal = Vertex_Normal;
+    gl_Position = ViewProj * v_WorldPosition;
+}
@@ -0,0 +1,31 @@
+#import bevy_pbr::mesh_view_bindings
+
+@group(1) @binding(0)
+var<uniform> health: f32;
+@group(1) @binding(1)
+var color_texture: texture_2d<f32>;
+@group(1) @binding(2)
+var color_sampler: sampler;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) world_position: vec4<f32>,
+    @location(1) world_normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+};
+
+@fragment
+fn fragment(input: VertexOutput) -> @location(0) vec4<f32> {
+    // the texture runs from empty on the left to full on the right,
+    // the whole bar takes the color at the current health
+    var color = textureSample(color_texture, color_sampler, vec2<f32>(health, 0.5)).rgb;
+
+    // pulse when it's nearly empty
+    if (health < 0.2) {
+        color = color * (cos(globals.time * 8.0) * 0.4 + 1.0);
+    }
+
+    // filled up to the health along the bar, dark past it
+    let filled = step(input.uv.x, health);
+    return vec4<f32>(mix(vec3<f32>(0.1), color, filled), 1.0);
+}
@@ -0,0 +1,56 @@
+#import bevy_pbr::mesh_view_bindings
+#import bevy_pbr::mesh_bindings
+#import bevy_pbr::mesh_functions
+
+#import "shaders/common.wgsl"
+
+struct HologramMaterial {
+    color: vec4<f32>,
+    scanline_density: f32,
+    scanline_speed: f32,
+    flicker: f32,
+    fresnel_power: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> material: HologramMaterial;
+
+struct Vertex {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) world_position: vec4<f32>,
+    @location(1) world_normal: vec3<f32>,
+};
+
+let TAU = 6.283185307179586;
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+    out.world_position = mesh_position_local_to_world(mesh.model, vec4<f32>(vertex.position, 1.0));
+    out.clip_position = mesh_position_world_to_clip(out.world_position);
+    out.world_normal = mesh_normal_local_to_world(vertex.normal);
+    return out;
+}
+
+@fragment
+fn fragment(input: VertexOutput) -> @location(0) vec4<f32> {
+    let normal = normalize(input.world_normal);
+    let to_camera = normalize(view.world_position - input.world_position.xyz);
+    // abs so the back faces of a two sided hologram get the same edge
+    let fresnel = pow(1.0 - abs(dot(normal, to_camera)), material.fresnel_power);
+
+    // in world space, so the lines stay level and evenly spaced whatever the mesh does
+    let height = input.world_position.y - globals.time * material.scanline_speed;
+    let scanline = smoothstep(0.2, 0.8, sin(height * material.scanline_density * TAU) * 0.5 + 0.5);
+
+    // a new brightness twenty times a second
+    let flicker = 1.0 - material.flicker * hash3(vec3<f32>(floor(globals.time * 20.0), 0.0, 0.0));
+
+    let alpha = material.color.a * (0.2 + 0.8 * fresnel) * (0.4 + 0.6 * scanline) * flicker;
+    return vec4<f32>(material.color.rgb, alpha);
+}
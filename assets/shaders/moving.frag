@@ -0,0 +1,23 @@
+#version 450
+// GLSL version of moving_frag.wgsl
+
+layout(location = 0) in vec4 v_WorldPosition;
+layout(location = 1) in vec3 v_WorldNormal;
+layout(location = 2) in vec2 v_Uv;
+
+layout(location = 0) out vec4 o_Target;
+
+layout(set = 1, binding = 1) uniform texture2D color_texture;
+layout(set = 1, binding = 2) uniform sampler color_sampler;
+layout(set = 1, binding = 3) uniform texture2D pattern_texture;
+layout(set = 1, binding = 4) uniform texture2D rock_texture;
+
+void main() {
+    // repeat the texture in world space projected top down, good for terrain
+    vec2 top_down_projection = v_WorldPosition.xz;
+    vec4 moss = texture(sampler2D(color_texture, color_sampler), top_down_projection);
+    vec4 rock = texture(sampler2D(rock_texture, color_sampler), top_down_projection);
+    vec4 pattern = texture(sampler2D(pattern_texture, color_sampler), v_Uv).xxxx;
+
+    o_Target = mix(rock, moss, pattern);
+}
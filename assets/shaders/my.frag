@@ -0,0 +1,38 @@
+#version 450
+// GLSL version of my_frag.wgsl
+
+layout(location = 0) in vec4 v_WorldPosition;
+layout(location = 1) in vec3 v_WorldNormal;
+layout(location = 2) in vec2 v_Uv;
+
+layout(location = 0) out vec4 o_Target;
+
+layout(set = 0, binding = 9) uniform Globals {
+    float time;
+    float delta_time;
+    uint frame_count;
+};
+
+const float TAU = 6.283185307179586;
+
+float inverse_lerp(float start, float end, float value) {
+    return (value - start) / (end - start);
+}
+
+vec4 cylinder_gradient(vec2 uv) {
+    vec4 color_a = vec4(1.0, 0.0, 0.0, 1.0);
+    vec4 color_b = vec4(0.0, 0.0, 1.0, 0.0);
+    float t = inverse_lerp(0.0, 1.0, uv.y);
+    return mix(color_a, color_b, t);
+}
+
+vec4 moving_waves(vec2 uv) {
+    float x_offset = cos(uv.x * TAU * 8.0) * 0.02;
+    float t = cos((uv.y + x_offset - time * 0.1) * TAU * 5.0) * 0.5 + 0.5;
+    t *= 1.0 - uv.y;
+    return vec4(t);
+}
+
+void main() {
+    o_Target = moving_waves(v_Uv) * cylinder_gradient(v_Uv);
+}
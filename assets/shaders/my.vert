@@ -0,0 +1,27 @@
+#version 450
+// GLSL version of my_vert.wgsl
+
+layout(location = 0) in vec3 Vertex_Position;
+layout(location = 1) in vec3 Vertex_Normal;
+layout(location = 2) in vec2 Vertex_Uv;
+
+layout(location = 0) out vec4 v_WorldPosition;
+layout(location = 1) out vec3 v_WorldNormal;
+layout(location = 2) out vec2 v_Uv;
+
+layout(set = 0, binding = 0) uniform View {
+    mat4 ViewProj;
+};
+
+layout(set = 2, binding = 0) uniform Mesh {
+    mat4 Model;
+    mat4 InverseTransposeModel;
+    uint flags;
+};
+
+void main() {
+    v_Uv = Vertex_Uv;
+    v_WorldPosition = Model * vec4(Vertex_Position, 1.0);
+    v_WorldNormal = Vertex_Normal;
+    gl_Position = ViewProj * v_WorldPosition;
+}
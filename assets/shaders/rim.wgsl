@@ -0,0 +1,46 @@
+#import bevy_pbr::mesh_view_bindings
+#import bevy_pbr::mesh_bindings
+#import bevy_pbr::mesh_functions
+
+struct RimMaterial {
+    color: vec4<f32>,
+    rim_color: vec4<f32>,
+    power: f32,
+    intensity: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> material: RimMaterial;
+
+struct Vertex {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) world_position: vec4<f32>,
+    @location(1) world_normal: vec3<f32>,
+};
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+    out.world_position = mesh_position_local_to_world(mesh.model, vec4<f32>(vertex.position, 1.0));
+    out.clip_position = mesh_position_world_to_clip(out.world_position);
+    // uses inverse_transpose_model, so scaled meshes keep their normals perpendicular to the surface
+    out.world_normal = mesh_normal_local_to_world(vertex.normal);
+    return out;
+}
+
+@fragment
+fn fragment(input: VertexOutput) -> @location(0) vec4<f32> {
+    let normal = normalize(input.world_normal);
+    let to_camera = normalize(view.world_position - input.world_position.xyz);
+
+    // 0 facing the camera, 1 at the silhouette
+    let fresnel = pow(1.0 - clamp(dot(normal, to_camera), 0.0, 1.0), material.power);
+    let rim = material.rim_color.rgb * fresnel * material.intensity;
+
+    return vec4<f32>(material.color.rgb + rim, material.color.a);
+}
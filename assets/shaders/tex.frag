@@ -5,13 +5,18 @@ layout(location = 0) out vec4 o_Target;
 
 layout(set = 1, binding = 0) uniform CustomMaterial {
     vec4 Color;
+    float AlphaCutoff;
 };
 
 layout(set = 1, binding = 1) uniform texture2D CustomMaterial_texture;
 layout(set = 1, binding = 2) uniform sampler CustomMaterial_sampler;
 
 void main() {
-    //o_Target = Color * texture(sampler2D(CustomMaterial_texture,CustomMaterial_sampler), v_Uv);
-    o_Target = texture(sampler2D(CustomMaterial_texture, CustomMaterial_sampler), v_Uv);
+    o_Target = Color * texture(sampler2D(CustomMaterial_texture, CustomMaterial_sampler), v_Uv);
+#ifdef ALPHA_MASK
+    if (o_Target.a < AlphaCutoff) {
+        discard;
+    }
+    o_Target.a = 1.0;
+#endif
 }
-
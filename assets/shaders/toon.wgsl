@@ -0,0 +1,109 @@
+#import bevy_pbr::mesh_view_bindings
+#import bevy_pbr::mesh_bindings
+
+#import bevy_pbr::utils
+#import bevy_pbr::clustered_forward
+#import bevy_pbr::lighting
+#import bevy_pbr::shadows
+
+struct ToonMaterial {
+    color: vec4<f32>,
+    specular_color: vec4<f32>,
+    rim_color: vec4<f32>,
+    bands: u32,
+    glossiness: f32,
+    specular_threshold: f32,
+    rim_threshold: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> material: ToonMaterial;
+@group(1) @binding(1)
+var ramp_texture: texture_2d<f32>;
+@group(1) @binding(2)
+var ramp_sampler: sampler;
+
+struct FragmentInput {
+    @builtin(position) frag_coord: vec4<f32>,
+    #import bevy_pbr::mesh_vertex_output
+};
+
+// the stepped brightness for a light hitting the surface at n_dot_l
+fn toon(n_dot_l: f32) -> vec3<f32> {
+#ifdef RAMP
+    // the lights are looped over, and implicit mip levels need uniform control flow
+    return textureSampleLevel(ramp_texture, ramp_sampler, vec2<f32>(n_dot_l, 0.5), 0.0).rgb;
+#else
+    let bands = f32(max(material.bands, 1u));
+    return vec3<f32>(ceil(n_dot_l * bands) / bands);
+#endif
+}
+
+// the hard edged blinn-phong highlight
+fn toon_specular(normal: vec3<f32>, to_light: vec3<f32>, to_camera: vec3<f32>, n_dot_l: f32) -> f32 {
+    let half_vector = normalize(to_light + to_camera);
+    let highlight = pow(saturate(dot(normal, half_vector)) * step(0.0, n_dot_l), material.glossiness);
+    return smoothstep(material.specular_threshold - 0.01, material.specular_threshold + 0.01, highlight);
+}
+
+@fragment
+fn fragment(in: FragmentInput) -> @location(0) vec4<f32> {
+    let normal = normalize(in.world_normal);
+    let to_camera = normalize(view.world_position - in.world_position.xyz);
+    let receives_shadows = (mesh.flags & MESH_FLAGS_SHADOW_RECEIVER_BIT) != 0u;
+
+    var diffuse = vec3<f32>(0.0);
+    var specular = vec3<f32>(0.0);
+
+    // the same light clusters the pbr shader walks
+    let is_orthographic = view.projection[3].w == 1.0;
+    let view_z = dot(vec4<f32>(
+        view.inverse_view[0].z,
+        view.inverse_view[1].z,
+        view.inverse_view[2].z,
+        view.inverse_view[3].z
+    ), in.world_position);
+    let cluster_index = fragment_cluster_index(in.frag_coord.xy, view_z, is_orthographic);
+    let offset_and_counts = unpack_offset_and_counts(cluster_index);
+
+    for (var i: u32 = offset_and_counts[0]; i < offset_and_counts[0] + offset_and_counts[1]; i = i + 1u) {
+        let light_id = get_light_id(i);
+        let light = point_lights.data[light_id];
+        let light_to_frag = light.position_radius.xyz - in.world_position.xyz;
+        let to_light = normalize(light_to_frag);
+        let attenuation = getDistanceAttenuation(
+            dot(light_to_frag, light_to_frag),
+            light.color_inverse_square_range.w
+        );
+        var shadow: f32 = 1.0;
+        if (receives_shadows && (light.flags & POINT_LIGHT_FLAGS_SHADOWS_ENABLED_BIT) != 0u) {
+            shadow = fetch_point_shadow(light_id, in.world_position, in.world_normal);
+        }
+        // shadows step like the rest of the light
+        let n_dot_l = saturate(dot(normal, to_light)) * step(0.5, shadow);
+        let light_color = light.color_inverse_square_range.rgb * attenuation / PI;
+        diffuse = diffuse + toon(n_dot_l) * light_color;
+        specular = specular + toon_specular(normal, to_light, to_camera, n_dot_l) * light_color;
+    }
+
+    for (var i: u32 = 0u; i < lights.n_directional_lights; i = i + 1u) {
+        let light = lights.directional_lights[i];
+        let to_light = light.direction_to_light;
+        var shadow: f32 = 1.0;
+        if (receives_shadows && (light.flags & DIRECTIONAL_LIGHT_FLAGS_SHADOWS_ENABLED_BIT) != 0u) {
+            shadow = fetch_directional_shadow(i, in.world_position, in.world_normal);
+        }
+        let n_dot_l = saturate(dot(normal, to_light)) * step(0.5, shadow);
+        let light_color = light.color.rgb / PI;
+        diffuse = diffuse + toon(n_dot_l) * light_color;
+        specular = specular + toon_specular(normal, to_light, to_camera, n_dot_l) * light_color;
+    }
+
+    let rim_amount = 1.0 - saturate(dot(normal, to_camera));
+    let rim = smoothstep(material.rim_threshold - 0.01, material.rim_threshold + 0.01, rim_amount);
+
+    let color = material.color.rgb * (diffuse + lights.ambient_color.rgb)
+        + material.specular_color.rgb * specular
+        + material.rim_color.rgb * rim;
+    return vec4<f32>(color, material.color.a);
+}
@@ -0,0 +1,43 @@
+#import bevy_pbr::mesh_view_bindings
+#import bevy_pbr::mesh_bindings
+#import bevy_pbr::mesh_functions
+
+struct ToonOutlineMaterial {
+    color: vec4<f32>,
+    width: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> material: ToonOutlineMaterial;
+
+struct Vertex {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+};
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+    let world_position = mesh_position_local_to_world(mesh.model, vec4<f32>(vertex.position, 1.0));
+    let clip_position = mesh_position_world_to_clip(world_position);
+
+    // push out on the screen rather than in the world, so the outline is as wide at any distance
+    let world_normal = mesh_normal_local_to_world(vertex.normal);
+    let clip_normal = (view.view_proj * vec4<f32>(world_normal, 0.0)).xy;
+    var offset = vec2<f32>(0.0);
+    if (dot(clip_normal, clip_normal) > 0.0) {
+        // clip space is 2 wide across the viewport, and gets divided by w afterwards
+        offset = normalize(clip_normal) / view.viewport.zw * 2.0 * material.width * clip_position.w;
+    }
+    out.clip_position = vec4<f32>(clip_position.xy + offset, clip_position.zw);
+    return out;
+}
+
+@fragment
+fn fragment() -> @location(0) vec4<f32> {
+    return material.color;
+}
@@ -0,0 +1,16 @@
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) world_position: vec4<f32>,
+    @location(1) world_normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+};
+
+@fragment
+fn fragment(input: VertexOutput) -> @location(0) vec4<f32> {
+    // a checkerboard of one unit cubes in world space, it stays put as the mesh moves through it
+    let cell = floor(input.world_position.xyz);
+    let checker = abs(cell.x + cell.y + cell.z) % 2.0;
+    let color_a = vec3<f32>(0.9, 0.9, 0.9);
+    let color_b = vec3<f32>(0.2, 0.2, 0.25);
+    return vec4<f32>(mix(color_a, color_b, checker), 1.0);
+}
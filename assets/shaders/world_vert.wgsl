@@ -0,0 +1,31 @@
+#import bevy_pbr::mesh_view_bindings
+#import bevy_pbr::mesh_bindings
+
+struct Vertex {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) world_position: vec4<f32>,
+    @location(1) world_normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+};
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+    out.uv = vertex.uv;
+
+    // local_to_world, the fragment shader works from this instead of the uvs
+    let world_position = mesh.model * vec4<f32>(vertex.position, 1.0);
+    out.world_position = world_position;
+    // world_to_clip
+    out.clip_position = view.view_proj * world_position;
+
+    out.world_normal = vertex.normal;
+
+    return out;
+}
@@ -0,0 +1,208 @@
+use std::path::Path;
+
+use bevy::{prelude::*, render::camera::ScalingMode};
+use bevy_inspector_egui::bevy_egui::EguiContext;
+use bevy_inspector_egui::quick::ResourceInspectorPlugin;
+use serde::{Deserialize, Serialize};
+
+use crate::camera::{ActiveCamera, CameraSystem, PanOrbitCamera};
+
+/// Shared with the team so everyone reviews from the same angles.
+const BOOKMARK_FILE: &str = "assets/camera_bookmarks.ron";
+
+/// Stores camera bookmarks with `Ctrl + 1..9` and recalls them with `1..9`.
+pub struct BookmarkPlugin;
+
+impl Plugin for BookmarkPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<CameraBookmarks>()
+            .insert_resource(CameraBookmarks::load(BOOKMARK_FILE))
+            .add_plugin(ResourceInspectorPlugin::<CameraBookmarks>::default())
+            .add_system(bookmark_keys.before(CameraSystem::Adjust))
+            .add_system(save_bookmarks.after(bookmark_keys));
+    }
+}
+
+#[derive(Reflect, FromReflect, Serialize, Deserialize, Clone, Debug)]
+pub enum BookmarkProjection {
+    Perspective {
+        fov: f32,
+        near: f32,
+        far: f32,
+    },
+    Orthographic {
+        scale: f32,
+        scaling_mode: ScalingMode,
+        near: f32,
+        far: f32,
+    },
+}
+
+/// A saved view of the scene.
+#[derive(Reflect, FromReflect, Serialize, Deserialize, Clone, Debug)]
+pub struct CameraBookmark {
+    pub name: String,
+    /// The number key that recalls this bookmark.
+    pub key: u8,
+    pub focus: Vec3,
+    pub radius: f32,
+    pub rotation: Quat,
+    pub projection: BookmarkProjection,
+}
+
+#[derive(Resource, Reflect, Serialize, Deserialize, Default)]
+#[reflect(Resource)]
+pub struct CameraBookmarks {
+    pub bookmarks: Vec<CameraBookmark>,
+}
+
+impl CameraBookmarks {
+    fn load(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        match std::fs::read_to_string(path) {
+            Ok(text) => ron::from_str(&text).unwrap_or_else(|err| {
+                warn!("could not parse {}: {}", path.display(), err);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    fn to_ron(&self) -> Option<String> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|err| warn!("could not serialize camera bookmarks: {}", err))
+            .ok()
+    }
+
+    pub fn get(&self, key: u8) -> Option<&CameraBookmark> {
+        self.bookmarks.iter().find(|bookmark| bookmark.key == key)
+    }
+
+    /// Replaces the bookmark on the same key, keeping its name.
+    pub fn store(&mut self, mut bookmark: CameraBookmark) {
+        match self.bookmarks.iter_mut().find(|b| b.key == bookmark.key) {
+            Some(existing) => {
+                bookmark.name = std::mem::take(&mut existing.name);
+                *existing = bookmark;
+            }
+            None => {
+                self.bookmarks.push(bookmark);
+                self.bookmarks.sort_by_key(|b| b.key);
+            }
+        }
+    }
+}
+
+const NUMBER_KEYS: [KeyCode; 9] = [
+    KeyCode::Key1,
+    KeyCode::Key2,
+    KeyCode::Key3,
+    KeyCode::Key4,
+    KeyCode::Key5,
+    KeyCode::Key6,
+    KeyCode::Key7,
+    KeyCode::Key8,
+    KeyCode::Key9,
+];
+
+fn bookmark_keys(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut egui_context: ResMut<EguiContext>,
+    mut bookmarks: ResMut<CameraBookmarks>,
+    active: Res<ActiveCamera>,
+    mut cameras: Query<(&mut PanOrbitCamera, &mut Transform, &mut Projection)>,
+) {
+    // typing a bookmark name in the inspector shouldn't move the camera
+    if egui_context.ctx_mut().wants_keyboard_input() {
+        return;
+    }
+    let Some(key) = NUMBER_KEYS
+        .iter()
+        .position(|key| keyboard_input.just_pressed(*key))
+        .map(|index| index as u8 + 1)
+    else {
+        return;
+    };
+    let Some(Ok((mut pan_orbit, mut transform, mut projection))) =
+        active.0.map(|entity| cameras.get_mut(entity))
+    else {
+        return;
+    };
+
+    if keyboard_input.any_pressed([KeyCode::LControl, KeyCode::RControl]) {
+        let projection = match projection.as_ref() {
+            Projection::Perspective(perspective) => BookmarkProjection::Perspective {
+                fov: perspective.fov,
+                near: perspective.near,
+                far: perspective.far,
+            },
+            Projection::Orthographic(orthographic) => BookmarkProjection::Orthographic {
+                scale: orthographic.scale,
+                scaling_mode: orthographic.scaling_mode.clone(),
+                near: orthographic.near,
+                far: orthographic.far,
+            },
+        };
+        bookmarks.store(CameraBookmark {
+            name: format!("view {}", key),
+            key,
+            focus: pan_orbit.focus,
+            radius: pan_orbit.radius,
+            rotation: transform.rotation,
+            projection,
+        });
+        info!("stored camera bookmark {}", key);
+    } else if let Some(bookmark) = bookmarks.get(key) {
+        pan_orbit.focus = bookmark.focus;
+        pan_orbit.radius = bookmark.radius;
+        transform.rotation = bookmark.rotation;
+        // the camera plugin's adjust is paused while egui has the pointer, so place the camera here
+        let rot_matrix = Mat3::from_quat(transform.rotation);
+        transform.translation =
+            pan_orbit.focus + rot_matrix.mul_vec3(Vec3::new(0.0, 0.0, pan_orbit.radius));
+        // the same check orbiting does when it starts, or horizontal drags go the wrong way
+        pan_orbit.upside_down = (bookmark.rotation * Vec3::Y).y <= 0.0;
+        // the window size parts are filled in again by bevy when the projection changes
+        *projection = match &bookmark.projection {
+            BookmarkProjection::Perspective { fov, near, far } => {
+                Projection::Perspective(PerspectiveProjection {
+                    fov: *fov,
+                    near: *near,
+                    far: *far,
+                    ..default()
+                })
+            }
+            BookmarkProjection::Orthographic {
+                scale,
+                scaling_mode,
+                near,
+                far,
+            } => Projection::Orthographic(OrthographicProjection {
+                scale: *scale,
+                scaling_mode: scaling_mode.clone(),
+                near: *near,
+                far: *far,
+                ..default()
+            }),
+        };
+    }
+}
+
+/// Saves stored bookmarks as well as names edited in the inspector. The inspector marks
+/// the resource changed whenever it's touched, so only write when the text is different.
+fn save_bookmarks(bookmarks: Res<CameraBookmarks>, mut saved: Local<Option<String>>) {
+    if !bookmarks.is_changed() {
+        return;
+    }
+    let Some(text) = bookmarks.to_ron() else {
+        return;
+    };
+    // the first run sees the bookmarks as they were loaded
+    if saved.is_some() && saved.as_ref() != Some(&text) {
+        if let Err(err) = std::fs::write(BOOKMARK_FILE, &text) {
+            warn!("could not write {}: {}", BOOKMARK_FILE, err);
+            return;
+        }
+    }
+    *saved = Some(text);
+}
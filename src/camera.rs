@@ -1,7 +1,9 @@
-use bevy::ecs::schedule::ShouldRun;
-use bevy::input::mouse::{MouseMotion, MouseWheel};
+use bevy::input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel};
+use bevy::input::touch::Touch;
 use bevy::prelude::*;
-use bevy_inspector_egui::bevy_egui::EguiContext;
+
+use crate::camera_path::CameraPath;
+use crate::input_ownership::{pointer_owned_by, InputOwnership, PointerConsumer};
 
 #[derive(Default)]
 pub struct CameraPlugin {
@@ -13,39 +15,161 @@ pub struct CameraRunCriteria;
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
 pub enum CameraSystem {
+    Select,
     PanOrbit,
     Adjust,
 }
 
-fn plugin_enabled(
-    mut egui_context: ResMut<EguiContext>,
-) -> ShouldRun {
-    // don't adjust camera if the mouse pointer in over an egui window
-    let ctx = egui_context.ctx_mut();
-    let pointer_over_area = ctx.is_pointer_over_area();
-    let using_pointer = ctx.is_using_pointer();
-    let wants_pointer = ctx.wants_pointer_input();
-    if wants_pointer || pointer_over_area || using_pointer {
-        ShouldRun::No
-    } else {
-        ShouldRun::Yes
-    }
+/// Line based scroll wheels are scaled to roughly match pixel based trackpad scrolling.
+const SCROLL_LINE_PIXELS: f32 = 50.0;
+
+const ALT_KEYS: [KeyCode; 2] = [KeyCode::LAlt, KeyCode::RAlt];
+
+/// Orbit drags use the middle button, or alt and the left button for trackpads without one.
+pub fn orbit_pressed(input_mouse: &Input<MouseButton>, keyboard_input: &Input<KeyCode>) -> bool {
+    input_mouse.pressed(MouseButton::Middle)
+        || (input_mouse.pressed(MouseButton::Left) && keyboard_input.any_pressed(ALT_KEYS))
 }
 
+/// The camera that currently receives mouse input, the one whose viewport is under the cursor.
+/// A drag keeps its camera until the orbit button is released.
+#[derive(Resource, Default)]
+pub struct ActiveCamera(pub Option<Entity>);
+
 impl Plugin for CameraPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system_set_to_stage(
-            CoreStage::Update,
-            SystemSet::new()
-                .with_run_criteria(plugin_enabled.label(CameraRunCriteria))
-                .with_system(pan_orbit_camera.label(CameraSystem::PanOrbit))
-                .with_system(
-                    adjust
-                        .label(CameraSystem::Adjust)
-                        .after(CameraSystem::PanOrbit),
-                )
-                .with_system(center_selection)
-        );
+        app.init_resource::<ActiveCamera>()
+            .add_system_to_stage(CoreStage::PreUpdate, update_viewports)
+            .add_system_set_to_stage(
+                CoreStage::Update,
+                SystemSet::new()
+                    .with_run_criteria(
+                        // don't adjust camera if the pointer belongs to egui or the gizmo
+                        pointer_owned_by(PointerConsumer::Camera).label(CameraRunCriteria),
+                    )
+                    .with_system(select_active_camera.label(CameraSystem::Select))
+                    .with_system(
+                        adjust
+                            .label(CameraSystem::Adjust)
+                            .after(CameraSystem::PanOrbit),
+                    )
+                    .with_system(center_selection.after(CameraSystem::Select)),
+            )
+            // runs outside the set so input meant for someone else is consumed, not replayed later
+            .add_system(
+                pan_orbit_camera
+                    .label(CameraSystem::PanOrbit)
+                    .after(CameraSystem::Select),
+            );
+    }
+}
+
+/// Places a camera's viewport in a fraction of the primary window,
+/// e.g. `position: (0.5, 0.0), size: (0.5, 1.0)` is the right half.
+/// The viewport is kept in sync with the window size.
+#[derive(Component)]
+pub struct OrbitViewport {
+    /// Top left corner, as a fraction of the window size.
+    pub position: Vec2,
+    /// Size as a fraction of the window size.
+    pub size: Vec2,
+}
+
+impl OrbitViewport {
+    pub fn left_half() -> Self {
+        OrbitViewport {
+            position: Vec2::ZERO,
+            size: Vec2::new(0.5, 1.0),
+        }
+    }
+
+    pub fn right_half() -> Self {
+        OrbitViewport {
+            position: Vec2::new(0.5, 0.0),
+            size: Vec2::new(0.5, 1.0),
+        }
+    }
+}
+
+fn update_viewports(
+    windows: Res<Windows>,
+    mut resize_events: EventReader<bevy::window::WindowResized>,
+    mut query: Query<(&mut Camera, &OrbitViewport, ChangeTrackers<OrbitViewport>)>,
+) {
+    let resized = resize_events.iter().count() > 0;
+    let Some(window) = windows.get_primary() else {
+        return;
+    };
+    let window_size = Vec2::new(
+        window.physical_width() as f32,
+        window.physical_height() as f32,
+    );
+    for (mut camera, viewport, tracker) in query.iter_mut() {
+        if !resized && !tracker.is_changed() {
+            continue;
+        }
+        let position = (viewport.position * window_size).as_uvec2();
+        let size = (viewport.size * window_size).as_uvec2().max(UVec2::ONE);
+        camera.viewport = Some(bevy::render::camera::Viewport {
+            physical_position: position,
+            physical_size: size,
+            ..default()
+        });
+    }
+}
+
+/// Picks the camera whose viewport contains the cursor, the highest priority one if they overlap.
+fn select_active_camera(
+    windows: Res<Windows>,
+    input_mouse: Res<Input<MouseButton>>,
+    keyboard_input: Res<Input<KeyCode>>,
+    touches: Res<Touches>,
+    mut active: ResMut<ActiveCamera>,
+    cameras: Query<(Entity, &Camera), With<PanOrbitCamera>>,
+) {
+    // a drag stays with the camera it started in
+    let dragging = (orbit_pressed(&input_mouse, &keyboard_input)
+        && !input_mouse.any_just_pressed([MouseButton::Middle, MouseButton::Left]))
+        || (touches.iter().next().is_some() && !touches.any_just_pressed());
+    if dragging && active.0.map_or(false, |entity| cameras.contains(entity)) {
+        return;
+    }
+
+    // touch positions are already from the top left
+    let cursor = touches.first_pressed_position().or_else(|| {
+        windows.get_primary().and_then(|window| {
+            window
+                .cursor_position()
+                // cursor_position is from the bottom left, viewports are from the top left
+                .map(|position| Vec2::new(position.x, window.height() - position.y))
+        })
+    });
+
+    let hovered = cursor.and_then(|cursor| {
+        cameras
+            .iter()
+            .filter(|(_, camera)| camera.is_active && cursor_in_viewport(camera, cursor))
+            .max_by_key(|(_, camera)| camera.priority)
+            .map(|(entity, _)| entity)
+    });
+
+    match hovered {
+        Some(entity) => active.0 = Some(entity),
+        // keep the last camera for keyboard input while the cursor is outside the window
+        None => {
+            if !active.0.map_or(false, |entity| cameras.contains(entity)) {
+                active.0 = cameras.iter().map(|(entity, _)| entity).next();
+            }
+        }
+    }
+}
+
+fn cursor_in_viewport(camera: &Camera, cursor: Vec2) -> bool {
+    match camera.logical_viewport_rect() {
+        Some((min, max)) => {
+            cursor.x >= min.x && cursor.x < max.x && cursor.y >= min.y && cursor.y < max.y
+        }
+        None => false,
     }
 }
 
@@ -53,6 +177,7 @@ fn center_selection(
     selection: Query<(&Transform, &bevy_mod_picking::Selection)>,
     mut camera: Query<(&mut PanOrbitCamera, &Transform)>,
     keyboard_input: Res<Input<KeyCode>>,
+    active: Res<ActiveCamera>,
 ) {
     if !selection.iter().any(|(_, selection)| selection.selected()) {
         return;
@@ -68,7 +193,9 @@ fn center_selection(
             }
         }
         let center = total / point_count as f32;
-        let (mut camera, camera_transform) = camera.single_mut();
+        let Some(Ok((mut camera, camera_transform))) = active.0.map(|entity| camera.get_mut(entity)) else {
+            return;
+        };
         camera.radius = (camera_transform.translation - center).length();
         camera.focus = center;
     }
@@ -81,6 +208,8 @@ pub struct PanOrbitCamera {
     pub focus: Vec3,
     pub radius: f32,
     pub upside_down: bool,
+    /// Linked cameras share their orientation and radius, orbiting one orbits them all.
+    pub linked: bool,
 }
 
 impl Default for PanOrbitCamera {
@@ -89,6 +218,7 @@ impl Default for PanOrbitCamera {
             focus: Vec3::ZERO,
             radius: 5.0,
             upside_down: false,
+            linked: false,
         }
     }
 }
@@ -98,12 +228,21 @@ fn pan_orbit_camera(
     mut ev_motion: EventReader<MouseMotion>,
     mut ev_scroll: EventReader<MouseWheel>,
     input_mouse: Res<Input<MouseButton>>,
-    mut query: Query<(&mut PanOrbitCamera, &mut Transform, &Projection)>,
+    touches: Res<Touches>,
+    mut query: Query<(Entity, &mut PanOrbitCamera, &mut Transform, &Projection, &Camera)>,
     keyboard_input: Res<Input<KeyCode>>,
+    active: Res<ActiveCamera>,
+    ownership: Res<InputOwnership>,
+    paths: Query<&CameraPath>,
 ) {
-    // change input mapping for orbit and panning here
-    let orbit_button = MouseButton::Middle;
-    let pan_button = MouseButton::Middle;
+    if !ownership.is_owned_by(PointerConsumer::Camera) {
+        ev_motion.iter().last();
+        ev_scroll.iter().last();
+        return;
+    }
+
+    // change input mapping for orbit and panning here, see `orbit_pressed` for the buttons
+    let orbit_buttons = [MouseButton::Middle, MouseButton::Left];
     let pan_key_left = KeyCode::LShift;
     let pan_key_right = KeyCode::RShift;
 
@@ -112,13 +251,13 @@ fn pan_orbit_camera(
     let mut scroll = 0.0;
     let mut orbit_button_changed = false;
 
-    if input_mouse.pressed(orbit_button)
-        && !(keyboard_input.pressed(pan_key_right) || keyboard_input.pressed(pan_key_left))
+    let dragging = orbit_pressed(&input_mouse, &keyboard_input);
+    if dragging && !(keyboard_input.pressed(pan_key_right) || keyboard_input.pressed(pan_key_left))
     {
         for ev in ev_motion.iter() {
             rotation_move += ev.delta;
         }
-    } else if input_mouse.pressed(pan_button)
+    } else if dragging
         && (keyboard_input.pressed(pan_key_right) || keyboard_input.pressed(pan_key_left))
     {
         // Pan only if we're not rotating at the moment
@@ -127,13 +266,50 @@ fn pan_orbit_camera(
         }
     }
     for ev in ev_scroll.iter() {
-        scroll += ev.y;
+        match ev.unit {
+            // a wheel notch should zoom about as far as a trackpad swipe
+            MouseScrollUnit::Line => {
+                scroll += ev.y * SCROLL_LINE_PIXELS;
+                // tilt wheels and some trackpad drivers scroll sideways in lines
+                pan.x -= ev.x * SCROLL_LINE_PIXELS;
+            }
+            MouseScrollUnit::Pixel => {
+                scroll += ev.y;
+                // trackpads scroll sideways as well, use that to pan
+                pan.x -= ev.x;
+            }
+        }
     }
-    if input_mouse.just_released(orbit_button) || input_mouse.just_pressed(orbit_button) {
+    if input_mouse.any_just_released(orbit_buttons) || input_mouse.any_just_pressed(orbit_buttons) {
         orbit_button_changed = true;
     }
 
-    for (mut pan_orbit, mut transform, projection) in query.iter_mut() {
+    // one finger orbits, two fingers pan and pinch to zoom
+    let fingers: Vec<&Touch> = touches.iter().collect();
+    match fingers.as_slice() {
+        [finger] => rotation_move += finger.delta(),
+        [first, second, ..] => {
+            pan += (first.delta() + second.delta()) / 2.0;
+            let distance = first.position().distance(second.position());
+            let previous_distance = first.previous_position().distance(second.previous_position());
+            scroll += distance - previous_distance;
+        }
+        [] => {}
+    }
+    if touches.any_just_pressed() || touches.any_just_released() {
+        orbit_button_changed = true;
+    }
+
+    let Some(active) = active.0 else {
+        return;
+    };
+    // a playing path owns the camera, the input is dropped rather than fighting it
+    if paths.get(active).map_or(false, |path| path.playing) {
+        return;
+    }
+    let mut link = None;
+
+    if let Ok((_, mut pan_orbit, mut transform, projection, camera)) = query.get_mut(active) {
         if orbit_button_changed {
             // only check for upside down when orbiting started or ended this frame
             // if the camera is "upside" down, panning horizontally would be inverted, so invert the input to make it correct
@@ -144,7 +320,7 @@ fn pan_orbit_camera(
         let mut any = false;
         if rotation_move.length_squared() > 0.0 {
             any = true;
-            let window = get_primary_window_size(&windows);
+            let window = get_viewport_size(camera, &windows);
             let delta_x = {
                 let delta = rotation_move.x / window.x * std::f32::consts::PI * 2.0;
                 if pan_orbit.upside_down {
@@ -161,7 +337,7 @@ fn pan_orbit_camera(
         } else if pan.length_squared() > 0.0 {
             any = true;
             // make panning distance independent of resolution and FOV,
-            let window = get_primary_window_size(&windows);
+            let window = get_viewport_size(camera, &windows);
             if let Projection::Perspective(projection) = projection {
                 pan *= Vec2::new(projection.fov * projection.aspect_ratio, projection.fov) / window;
             }
@@ -171,7 +347,9 @@ fn pan_orbit_camera(
             // make panning proportional to distance away from focus point
             let translation = (right + up) * pan_orbit.radius;
             pan_orbit.focus += translation;
-        } else if scroll.abs() > 0.0 {
+        }
+        // pinching and trackpad scrolling can pan and zoom at the same time
+        if scroll.abs() > 0.0 {
             any = true;
             pan_orbit.radius -= scroll * pan_orbit.radius * 0.002;
             // dont allow zoom to reach zero or you get stuck
@@ -185,6 +363,19 @@ fn pan_orbit_camera(
             let rot_matrix = Mat3::from_quat(transform.rotation);
             transform.translation =
                 pan_orbit.focus + rot_matrix.mul_vec3(Vec3::new(0.0, 0.0, pan_orbit.radius));
+            if pan_orbit.linked {
+                link = Some((transform.rotation, pan_orbit.radius));
+            }
+        }
+    }
+
+    // the translation of the linked cameras is updated by adjust
+    if let Some((rotation, radius)) = link {
+        for (entity, mut pan_orbit, mut transform, _, _) in query.iter_mut() {
+            if entity != active && pan_orbit.linked {
+                transform.rotation = rotation;
+                pan_orbit.radius = radius;
+            }
         }
     }
 }
@@ -197,6 +388,12 @@ fn adjust(mut query: Query<(&mut PanOrbitCamera, &mut Transform)>) {
     }
 }
 
+fn get_viewport_size(camera: &Camera, windows: &Res<Windows>) -> Vec2 {
+    camera
+        .logical_viewport_size()
+        .unwrap_or_else(|| get_primary_window_size(windows))
+}
+
 fn get_primary_window_size(windows: &Res<Windows>) -> Vec2 {
     let window = windows.get_primary().unwrap();
     let window = Vec2::new(window.width() as f32, window.height() as f32);
@@ -0,0 +1,297 @@
+use std::ops::{Add, Mul, Sub};
+
+use bevy::prelude::*;
+use bevy::time::FixedTimestep;
+use bevy::transform::TransformSystem;
+
+use crate::camera::PanOrbitCamera;
+
+/// Paths advance by this many seconds per step, regardless of the frame rate,
+/// so recordings of the same path always show the same frames.
+pub const CAMERA_PATH_STEP: f32 = 1.0 / 60.0;
+
+/// Drives [`PanOrbitCamera`]s that have a [`CameraPath`], `P` pauses and resumes them.
+pub struct CameraPathPlugin;
+
+impl Plugin for CameraPathPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<CameraPath>()
+            .add_system(toggle_camera_paths)
+            .add_system_set_to_stage(
+                CoreStage::PostUpdate,
+                SystemSet::new()
+                    .with_run_criteria(FixedTimestep::step(CAMERA_PATH_STEP as f64))
+                    .with_system(advance_camera_paths.before(TransformSystem::TransformPropagate)),
+            );
+    }
+}
+
+/// One control point of a spline path, the orbit is described the same way as [`PanOrbitCamera`].
+#[derive(Reflect, FromReflect, Clone, Copy, Debug, Default)]
+pub struct CameraKeyframe {
+    pub focus: Vec3,
+    pub radius: f32,
+    /// Rotation around the global Y axis in radians.
+    pub yaw: f32,
+    /// Rotation around the camera's X axis in radians, negative looks down.
+    pub pitch: f32,
+}
+
+impl CameraKeyframe {
+    pub fn new(focus: Vec3, radius: f32, yaw: f32, pitch: f32) -> Self {
+        CameraKeyframe {
+            focus,
+            radius,
+            yaw,
+            pitch,
+        }
+    }
+}
+
+#[derive(Reflect, FromReflect, Clone, Debug)]
+pub enum CameraPathKind {
+    /// Orbit around the current focus at a fixed rate.
+    Turntable { degrees_per_second: f32 },
+    /// A Catmull-Rom spline through the keyframes, taking `segment_seconds` between each pair.
+    Spline {
+        keyframes: Vec<CameraKeyframe>,
+        segment_seconds: f32,
+    },
+}
+
+impl Default for CameraPathKind {
+    fn default() -> Self {
+        CameraPathKind::Turntable {
+            degrees_per_second: 30.0,
+        }
+    }
+}
+
+#[derive(Reflect, FromReflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PathLooping {
+    /// Stop at the last keyframe.
+    #[default]
+    Once,
+    /// Jump back to the start, a looping spline also passes from the last keyframe back to the first.
+    Loop,
+    /// Play forwards then backwards.
+    PingPong,
+}
+
+#[derive(Reflect, FromReflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PathEasing {
+    #[default]
+    Linear,
+    /// Slow in and out over the whole path.
+    SmoothStep,
+}
+
+impl PathEasing {
+    fn ease(&self, t: f32) -> f32 {
+        match self {
+            PathEasing::Linear => t,
+            PathEasing::SmoothStep => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// Scripted motion for a [`PanOrbitCamera`], for deterministic turntable recordings.
+#[derive(Component, Reflect, Default, Clone, Debug)]
+#[reflect(Component)]
+pub struct CameraPath {
+    pub kind: CameraPathKind,
+    pub looping: PathLooping,
+    pub easing: PathEasing,
+    pub playing: bool,
+    /// Seconds since the path started.
+    pub elapsed: f32,
+}
+
+impl CameraPath {
+    pub fn turntable(degrees_per_second: f32) -> Self {
+        CameraPath {
+            kind: CameraPathKind::Turntable { degrees_per_second },
+            playing: true,
+            ..default()
+        }
+    }
+
+    pub fn spline(keyframes: Vec<CameraKeyframe>, segment_seconds: f32) -> Self {
+        CameraPath {
+            kind: CameraPathKind::Spline {
+                keyframes,
+                segment_seconds,
+            },
+            playing: true,
+            ..default()
+        }
+    }
+
+    /// Seconds for one pass over the path, a full turn for turntables.
+    /// `None` when the path doesn't move.
+    fn duration(&self) -> Option<f32> {
+        let duration = match &self.kind {
+            CameraPathKind::Turntable { degrees_per_second } => 360.0 / degrees_per_second.abs(),
+            CameraPathKind::Spline {
+                keyframes,
+                segment_seconds,
+            } => {
+                if keyframes.len() < 2 {
+                    return None;
+                }
+                self.segments(keyframes) as f32 * segment_seconds
+            }
+        };
+        (duration.is_finite() && duration > 0.0).then_some(duration)
+    }
+
+    fn segments(&self, keyframes: &[CameraKeyframe]) -> usize {
+        if self.looping == PathLooping::Loop {
+            keyframes.len()
+        } else {
+            keyframes.len() - 1
+        }
+    }
+
+    /// How far along the pass `elapsed` is, 0..1 with the looping and easing applied.
+    fn progress(&self, duration: f32) -> f32 {
+        let t = match self.looping {
+            PathLooping::Once => (self.elapsed / duration).min(1.0),
+            PathLooping::Loop => (self.elapsed / duration).fract(),
+            PathLooping::PingPong => {
+                let t = (self.elapsed / duration) % 2.0;
+                if t > 1.0 {
+                    2.0 - t
+                } else {
+                    t
+                }
+            }
+        };
+        self.easing.ease(t)
+    }
+
+    /// Keeps `elapsed` within the path, so it doesn't grow forever and lose precision.
+    fn wrap_elapsed(&mut self, duration: f32) {
+        self.elapsed = match self.looping {
+            PathLooping::Once => self.elapsed.min(duration),
+            PathLooping::Loop => self.elapsed % duration,
+            PathLooping::PingPong => self.elapsed % (duration * 2.0),
+        };
+    }
+
+    /// The turntable angle at `elapsed` in radians, wrapped to one turn.
+    fn turntable_angle(&self, degrees_per_second: f32) -> f32 {
+        match self.duration() {
+            Some(duration) => {
+                self.progress(duration) * std::f32::consts::TAU * degrees_per_second.signum()
+            }
+            None => 0.0,
+        }
+    }
+
+    /// The keyframe at `elapsed` for spline paths.
+    fn sample(&self, keyframes: &[CameraKeyframe]) -> Option<CameraKeyframe> {
+        let Some(duration) = self.duration() else {
+            return keyframes.first().copied();
+        };
+        let closed = self.looping == PathLooping::Loop;
+        let segments = self.segments(keyframes);
+        let t = self.progress(duration) * segments as f32;
+        let segment = (t.floor() as usize).min(segments - 1);
+        let local_t = t - segment as f32;
+
+        let count = keyframes.len() as isize;
+        let point = |index: isize| {
+            if closed {
+                keyframes[index.rem_euclid(count) as usize]
+            } else {
+                keyframes[index.clamp(0, count - 1) as usize]
+            }
+        };
+        let segment = segment as isize;
+        let (p0, p1, p2, p3) = (
+            point(segment - 1),
+            point(segment),
+            point(segment + 1),
+            point(segment + 2),
+        );
+
+        Some(CameraKeyframe {
+            focus: catmull_rom(p0.focus, p1.focus, p2.focus, p3.focus, local_t),
+            radius: catmull_rom(p0.radius, p1.radius, p2.radius, p3.radius, local_t).max(0.05),
+            yaw: catmull_rom(p0.yaw, p1.yaw, p2.yaw, p3.yaw, local_t),
+            pitch: catmull_rom(p0.pitch, p1.pitch, p2.pitch, p3.pitch, local_t),
+        })
+    }
+}
+
+fn catmull_rom<T>(p0: T, p1: T, p2: T, p3: T, t: f32) -> T
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<f32, Output = T>,
+{
+    let t2 = t * t;
+    let t3 = t2 * t;
+    (p1 * 2.0
+        + (p2 - p0) * t
+        + (p0 * 2.0 - p1 * 5.0 + p2 * 4.0 - p3) * t2
+        + (p1 * 3.0 - p0 - p2 * 3.0 + p3) * t3)
+        * 0.5
+}
+
+fn toggle_camera_paths(keyboard_input: Res<Input<KeyCode>>, mut paths: Query<&mut CameraPath>) {
+    if keyboard_input.just_pressed(KeyCode::P) {
+        for mut path in paths.iter_mut() {
+            path.playing = !path.playing;
+            // a path that played to its end starts over
+            let finished = path
+                .duration()
+                .map_or(false, |duration| path.elapsed >= duration);
+            if path.playing && path.looping == PathLooping::Once && finished {
+                path.elapsed = 0.0;
+            }
+        }
+    }
+}
+
+fn advance_camera_paths(mut query: Query<(&mut CameraPath, &mut PanOrbitCamera, &mut Transform)>) {
+    for (mut path, mut pan_orbit, mut transform) in query.iter_mut() {
+        if !path.playing {
+            continue;
+        }
+        let previous_angle = match path.kind {
+            CameraPathKind::Turntable { degrees_per_second } => {
+                path.turntable_angle(degrees_per_second)
+            }
+            CameraPathKind::Spline { .. } => 0.0,
+        };
+        path.elapsed += CAMERA_PATH_STEP;
+        if let Some(duration) = path.duration() {
+            path.wrap_elapsed(duration);
+            // stopped so the camera can be orbited again, `P` starts it over
+            if path.looping == PathLooping::Once && path.elapsed >= duration {
+                path.playing = false;
+            }
+        }
+
+        match &path.kind {
+            CameraPathKind::Turntable { degrees_per_second } => {
+                // turn by the change in angle, so the orbit starts from wherever the camera was
+                let angle = path.turntable_angle(*degrees_per_second) - previous_angle;
+                transform.rotation = Quat::from_rotation_y(angle) * transform.rotation;
+            }
+            CameraPathKind::Spline { keyframes, .. } => {
+                if let Some(keyframe) = path.sample(keyframes) {
+                    pan_orbit.focus = keyframe.focus;
+                    pan_orbit.radius = keyframe.radius;
+                    transform.rotation =
+                        Quat::from_rotation_y(keyframe.yaw) * Quat::from_rotation_x(keyframe.pitch);
+                }
+            }
+        }
+
+        // the camera plugin's adjust is paused while egui has the pointer, so place the camera here
+        let rot_matrix = Mat3::from_quat(transform.rotation);
+        transform.translation =
+            pan_orbit.focus + rot_matrix.mul_vec3(Vec3::new(0.0, 0.0, pan_orbit.radius));
+    }
+}
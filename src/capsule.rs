@@ -0,0 +1,182 @@
+use bevy::{
+    prelude::{Component, Mesh, Reflect, ReflectComponent},
+    render::{mesh::Indices, render_resource::PrimitiveTopology},
+};
+
+/// A capsule standing on its end along the Y axis, a cylinder with a hemisphere on each end.
+/// The V coordinate follows the distance along the surface from the bottom pole
+/// so stripes keep an even spacing across the hemispheres.
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component)]
+pub struct Capsule {
+    /// Radius of the cylinder and the hemispheres.
+    pub radius: f32,
+    /// Height of the cylinder part, the total height is `depth + 2 * radius`.
+    pub depth: f32,
+    /// Number of vertices around each horizontal slice of the capsule.
+    pub resolution: u32,
+    /// Number of latitude rings in each hemisphere.
+    pub rings: u32,
+    /// Number of vertical segments in the cylinder part.
+    pub segments: u32,
+}
+
+impl Default for Capsule {
+    fn default() -> Self {
+        Self {
+            radius: 0.5,
+            depth: 1.0,
+            resolution: 32,
+            rings: 8,
+            segments: 2,
+        }
+    }
+}
+
+impl From<Capsule> for Mesh {
+    fn from(c: Capsule) -> Self {
+        debug_assert!(c.radius > 0.0);
+        debug_assert!(c.depth >= 0.0);
+        debug_assert!(c.resolution > 2);
+        debug_assert!(c.rings > 0);
+        debug_assert!(c.segments > 0);
+
+        // the profile from the bottom pole to the top pole as (y, radius, normal y, normal radius)
+        let mut profile: Vec<(f32, f32, f32, f32)> = Vec::new();
+        let half_depth = c.depth / 2.0;
+        let step_phi = std::f32::consts::FRAC_PI_2 / c.rings as f32;
+
+        for i in 0..=c.rings {
+            let phi = -std::f32::consts::FRAC_PI_2 + i as f32 * step_phi;
+            let (sin, cos) = phi.sin_cos();
+            profile.push((-half_depth + c.radius * sin, c.radius * cos, sin, cos));
+        }
+        // without depth the two hemispheres share the equator
+        if c.depth > 0.0 {
+            for i in 1..c.segments {
+                let y = -half_depth + c.depth * i as f32 / c.segments as f32;
+                profile.push((y, c.radius, 0.0, 1.0));
+            }
+        }
+        let first_top = if c.depth > 0.0 { 0 } else { 1 };
+        for i in first_top..=c.rings {
+            let phi = i as f32 * step_phi;
+            let (sin, cos) = phi.sin_cos();
+            profile.push((half_depth + c.radius * sin, c.radius * cos, sin, cos));
+        }
+        // the poles are exact
+        profile[0].1 = 0.0;
+        profile.last_mut().unwrap().1 = 0.0;
+
+        let mut lengths = Vec::with_capacity(profile.len());
+        let mut length = 0.0;
+        for (i, &(y, radius, _, _)) in profile.iter().enumerate() {
+            if i > 0 {
+                let (prev_y, prev_radius, _, _) = profile[i - 1];
+                length += ((y - prev_y).powi(2) + (radius - prev_radius).powi(2)).sqrt();
+            }
+            lengths.push(length);
+        }
+
+        let num_rings = profile.len() as u32;
+        let num_vertices = num_rings * (c.resolution + 1);
+        let num_indices = (num_rings - 2) * c.resolution * 6;
+
+        let mut positions = Vec::with_capacity(num_vertices as usize);
+        let mut normals = Vec::with_capacity(num_vertices as usize);
+        let mut uvs = Vec::with_capacity(num_vertices as usize);
+        let mut indices = Vec::with_capacity(num_indices as usize);
+
+        let step_theta = std::f32::consts::TAU / c.resolution as f32;
+
+        for (&(y, radius, normal_y, normal_radius), &length_so_far) in profile.iter().zip(&lengths)
+        {
+            for segment in 0..=c.resolution {
+                let theta = segment as f32 * step_theta;
+                let (sin, cos) = theta.sin_cos();
+
+                positions.push([radius * cos, y, radius * sin]);
+                normals.push([normal_radius * cos, normal_y, normal_radius * sin]);
+                uvs.push([segment as f32 / c.resolution as f32, length_so_far / length]);
+            }
+        }
+
+        // the pole rings are a single point repeated, skip the triangles without area there
+
+        for i in 0..num_rings - 1 {
+            let ring = i * (c.resolution + 1);
+            let next_ring = (i + 1) * (c.resolution + 1);
+
+            for j in 0..c.resolution {
+                if i != 0 {
+                    indices.extend_from_slice(&[ring + j, next_ring + j, ring + j + 1]);
+                }
+                if i + 1 != num_rings - 1 {
+                    indices.extend_from_slice(&[next_ring + j, next_ring + j + 1, ring + j + 1]);
+                }
+            }
+        }
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.set_indices(Some(Indices::U32(indices)));
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        mesh
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::{default, Mesh, Vec3};
+    use bevy::render::mesh::VertexAttributeValues;
+
+    use super::Capsule;
+
+    #[test]
+    fn surface_is_radius_away_from_the_axis() {
+        let capsules = [
+            Capsule::default(),
+            Capsule {
+                depth: 0.0,
+                ..default()
+            },
+            Capsule {
+                radius: 0.25,
+                depth: 4.0,
+                resolution: 12,
+                rings: 3,
+                segments: 8,
+            },
+        ];
+        for capsule in capsules {
+            let radius = capsule.radius;
+            let half_depth = capsule.depth / 2.0;
+            let mesh = Mesh::from(capsule);
+            let positions = mesh
+                .attribute(Mesh::ATTRIBUTE_POSITION)
+                .and_then(VertexAttributeValues::as_float3)
+                .unwrap();
+            let mut max_y = f32::MIN;
+            for &[x, y, z] in positions {
+                let on_axis = Vec3::new(0.0, y.clamp(-half_depth, half_depth), 0.0);
+                let distance = Vec3::new(x, y, z).distance(on_axis);
+                assert!((distance - radius).abs() < 1e-5, "{distance} from the axis");
+                max_y = max_y.max(y);
+            }
+            assert!((max_y - (half_depth + radius)).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn v_runs_from_pole_to_pole() {
+        let mesh = Mesh::from(Capsule::default());
+        let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute(Mesh::ATTRIBUTE_UV_0)
+        else {
+            panic!("expected float2 uvs");
+        };
+        assert_eq!(uvs.first().unwrap()[1], 0.0);
+        assert!((uvs.last().unwrap()[1] - 1.0).abs() < 1e-6);
+        assert!(uvs.windows(2).all(|pair| pair[0][1] <= pair[1][1]));
+    }
+}
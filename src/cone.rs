@@ -0,0 +1,193 @@
+use bevy::{
+    prelude::{Component, Mesh, Reflect, ReflectComponent, Vec3},
+    render::{mesh::Indices, render_resource::PrimitiveTopology},
+};
+
+/// A cone or frustum which stands on the XZ plane, a top radius of 0 makes a pointed cone.
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component)]
+pub struct Cone {
+    /// Radius of the bottom in the XZ plane.
+    pub bottom_radius: f32,
+    /// Radius of the top in the XZ plane.
+    pub top_radius: f32,
+    /// Height of the cone in the Y axis.
+    pub height: f32,
+    /// Number of vertices around each horizontal slice of the cone.
+    pub resolution: u32,
+    /// Number of vertical segments.
+    pub segments: u32,
+}
+
+impl Default for Cone {
+    fn default() -> Self {
+        Self {
+            bottom_radius: 0.5,
+            top_radius: 0.0,
+            height: 1.0,
+            resolution: 16,
+            segments: 4,
+        }
+    }
+}
+
+impl From<Cone> for Mesh {
+    fn from(c: Cone) -> Self {
+        debug_assert!(c.bottom_radius >= 0.0);
+        debug_assert!(c.top_radius >= 0.0);
+        debug_assert!(c.bottom_radius > 0.0 || c.top_radius > 0.0);
+        debug_assert!(c.height > 0.0);
+        debug_assert!(c.resolution > 2);
+        debug_assert!(c.segments > 0);
+
+        let num_rings = c.segments + 1;
+        let num_vertices = num_rings * (c.resolution + 1) + 2 * (c.resolution + 2);
+        let num_indices = (2 * c.segments * c.resolution + 2 * c.resolution) * 3;
+
+        let mut positions = Vec::with_capacity(num_vertices as usize);
+        let mut normals = Vec::with_capacity(num_vertices as usize);
+        let mut uvs = Vec::with_capacity(num_vertices as usize);
+        let mut indices = Vec::with_capacity(num_indices as usize);
+
+        let step_theta = std::f32::consts::TAU / c.resolution as f32;
+        let step_y = c.height / c.segments as f32;
+        // the side leans in by the difference in radii over the height
+        let slope = (c.bottom_radius - c.top_radius) / c.height;
+        let radius_at = |ring: u32| {
+            let t = ring as f32 / c.segments as f32;
+            c.bottom_radius + (c.top_radius - c.bottom_radius) * t
+        };
+
+        // rings
+
+        for ring in 0..num_rings {
+            let y = -c.height / 2.0 + ring as f32 * step_y;
+            let radius = radius_at(ring);
+
+            for segment in 0..=c.resolution {
+                let theta = segment as f32 * step_theta;
+                let (sin, cos) = theta.sin_cos();
+
+                positions.push([radius * cos, y, radius * sin]);
+                normals.push(Vec3::new(cos, slope, sin).normalize().to_array());
+                uvs.push([
+                    segment as f32 / c.resolution as f32,
+                    ring as f32 / c.segments as f32,
+                ]);
+            }
+        }
+
+        // sides, a ring with no radius is the tip so only one triangle of each quad has area
+
+        for i in 0..c.segments {
+            let ring = i * (c.resolution + 1);
+            let next_ring = (i + 1) * (c.resolution + 1);
+
+            for j in 0..c.resolution {
+                if radius_at(i) > 0.0 {
+                    indices.extend_from_slice(&[ring + j, next_ring + j, ring + j + 1]);
+                }
+                if radius_at(i + 1) > 0.0 {
+                    indices.extend_from_slice(&[next_ring + j, next_ring + j + 1, ring + j + 1]);
+                }
+            }
+        }
+
+        // caps
+
+        let mut build_cap = |top: bool| {
+            let radius = if top { c.top_radius } else { c.bottom_radius };
+            if radius <= 0.0 {
+                return;
+            }
+            let offset = positions.len() as u32;
+            let (y, normal_y) = if top {
+                (c.height / 2., 1.)
+            } else {
+                (c.height / -2., -1.)
+            };
+
+            positions.push([0.0, y, 0.0]);
+            normals.push([0.0, normal_y, 0.0]);
+            uvs.push([0.5, 0.5]);
+
+            for i in 0..=c.resolution {
+                let theta = i as f32 * step_theta;
+                let (sin, cos) = theta.sin_cos();
+
+                positions.push([cos * radius, y, sin * radius]);
+                normals.push([0.0, normal_y, 0.0]);
+                uvs.push([0.5 * (cos + 1.0), 1.0 - 0.5 * (sin + 1.0)]);
+            }
+
+            for i in 1..=c.resolution {
+                if top {
+                    indices.extend_from_slice(&[offset, offset + i + 1, offset + i]);
+                } else {
+                    indices.extend_from_slice(&[offset, offset + i, offset + i + 1]);
+                }
+            }
+        };
+
+        build_cap(true);
+        build_cap(false);
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.set_indices(Some(Indices::U32(indices)));
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        mesh
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::{default, Mesh, Vec2};
+    use bevy::render::mesh::VertexAttributeValues;
+
+    use super::Cone;
+
+    #[test]
+    fn fits_its_height_and_radii() {
+        let cones = [
+            Cone::default(),
+            Cone {
+                bottom_radius: 1.0,
+                top_radius: 0.25,
+                height: 2.0,
+                resolution: 24,
+                segments: 6,
+            },
+            Cone {
+                bottom_radius: 0.0,
+                top_radius: 0.5,
+                ..default()
+            },
+        ];
+        for cone in cones {
+            let half_height = cone.height / 2.0;
+            let max_radius = cone.bottom_radius.max(cone.top_radius);
+            let mesh = Mesh::from(cone);
+            let positions = mesh
+                .attribute(Mesh::ATTRIBUTE_POSITION)
+                .and_then(VertexAttributeValues::as_float3)
+                .unwrap();
+            for &[x, y, z] in positions {
+                assert!(y.abs() <= half_height + 1e-6, "{y} is off the cone");
+                assert!(Vec2::new(x, z).length() <= max_radius + 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn pointed_cone_has_no_top_cap() {
+        let mesh = Mesh::from(Cone::default());
+        let normals = mesh
+            .attribute(Mesh::ATTRIBUTE_NORMAL)
+            .and_then(VertexAttributeValues::as_float3)
+            .unwrap();
+        assert!(!normals.contains(&[0.0, 1.0, 0.0]));
+        assert!(normals.contains(&[0.0, -1.0, 0.0]));
+    }
+}
@@ -0,0 +1,101 @@
+use bevy::{
+    prelude::{Component, Mesh, Reflect, ReflectComponent, Vec3},
+    render::{mesh::Indices, render_resource::PrimitiveTopology},
+};
+
+/// A sphere made from a subdivided cube pushed out onto the sphere, also called a quad sphere.
+/// Each face gets the whole 0..1 UV range like [`crate::plane::SubdividedPlane`],
+/// so the seams are only at the cube edges and nothing pinches at the poles.
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component)]
+pub struct CubeSphere {
+    pub radius: f32,
+    /// Number of extra vertices along each edge of a cube face.
+    pub subdivisions: u32,
+}
+
+impl Default for CubeSphere {
+    fn default() -> Self {
+        Self {
+            radius: 0.5,
+            subdivisions: 8,
+        }
+    }
+}
+
+impl From<CubeSphere> for Mesh {
+    fn from(sphere: CubeSphere) -> Self {
+        debug_assert!(sphere.radius > 0.0);
+
+        let edge_vertex_count = sphere.subdivisions + 2;
+        let face_vertex_count = edge_vertex_count * edge_vertex_count;
+        let num_vertices = (6 * face_vertex_count) as usize;
+        let num_indices = (6 * (edge_vertex_count - 1) * (edge_vertex_count - 1) * 6) as usize;
+
+        let mut positions: Vec<[f32; 3]> = Vec::with_capacity(num_vertices);
+        let mut normals: Vec<[f32; 3]> = Vec::with_capacity(num_vertices);
+        let mut uvs: Vec<[f32; 2]> = Vec::with_capacity(num_vertices);
+        let mut indices: Vec<u32> = Vec::with_capacity(num_indices);
+
+        // face normal and the face's second axis, the first axis is normal x second
+        let faces = [
+            (Vec3::X, Vec3::Y),
+            (Vec3::NEG_X, Vec3::Y),
+            (Vec3::Y, Vec3::Z),
+            (Vec3::NEG_Y, Vec3::Z),
+            (Vec3::Z, Vec3::Y),
+            (Vec3::NEG_Z, Vec3::Y),
+        ];
+
+        for (face, (normal, axis_b)) in faces.iter().enumerate() {
+            let axis_a = normal.cross(*axis_b);
+            let offset = face as u32 * face_vertex_count;
+
+            for y in 0..edge_vertex_count {
+                for x in 0..edge_vertex_count {
+                    let tx = x as f32 / (edge_vertex_count - 1) as f32;
+                    let ty = y as f32 / (edge_vertex_count - 1) as f32;
+                    let cube = *normal + axis_a * (tx * 2.0 - 1.0) + *axis_b * (ty * 2.0 - 1.0);
+                    let point = spherify(cube);
+
+                    positions.push((point * sphere.radius).to_array());
+                    normals.push(point.to_array());
+                    uvs.push([tx, 1.0 - ty]);
+                }
+            }
+
+            for y in 0..edge_vertex_count - 1 {
+                for x in 0..edge_vertex_count - 1 {
+                    let quad = offset + y * edge_vertex_count + x;
+                    indices.push(quad + edge_vertex_count + 1);
+                    indices.push(quad + 1);
+                    indices.push(quad + edge_vertex_count);
+                    indices.push(quad);
+                    indices.push(quad + edge_vertex_count);
+                    indices.push(quad + 1);
+                }
+            }
+        }
+
+        assert_eq!(num_vertices, positions.len());
+        assert_eq!(num_indices, indices.len());
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.set_indices(Some(Indices::U32(indices)));
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        mesh
+    }
+}
+
+/// Maps a point on the cube to the unit sphere, spreading the vertices more evenly than normalizing.
+// http://mathproofs.blogspot.com/2005/07/mapping-cube-to-sphere.html
+fn spherify(p: Vec3) -> Vec3 {
+    let (x2, y2, z2) = (p.x * p.x, p.y * p.y, p.z * p.z);
+    Vec3::new(
+        p.x * (1.0 - y2 / 2.0 - z2 / 2.0 + y2 * z2 / 3.0).sqrt(),
+        p.y * (1.0 - z2 / 2.0 - x2 / 2.0 + z2 * x2 / 3.0).sqrt(),
+        p.z * (1.0 - x2 / 2.0 - y2 / 2.0 + x2 * y2 / 3.0).sqrt(),
+    )
+}
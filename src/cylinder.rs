@@ -1,11 +1,15 @@
 // https://github.com/rparrett/typey_birb/blob/main/src/cylinder.rs
 
 use bevy::{
-    prelude::Mesh,
+    prelude::{Component, Mesh, Reflect, ReflectComponent},
     render::{mesh::Indices, render_resource::PrimitiveTopology},
 };
 
+use crate::vertex_colors::VertexColors;
+
 /// A cylinder which stands on the XZ plane
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component)]
 pub struct Cylinder {
     /// Radius in the XZ plane.
     pub radius: f32,
@@ -15,6 +19,8 @@ pub struct Cylinder {
     pub resolution: u32,
     // Number of vertical segments.
     pub segments: u32,
+    /// Painted colors for debugging the shading, gradients run up the barrel.
+    pub colors: VertexColors,
 }
 
 impl Default for Cylinder {
@@ -24,6 +30,7 @@ impl Default for Cylinder {
             height: 1.0,
             resolution: 16,
             segments: 4,
+            colors: VertexColors::None,
         }
     }
 }
@@ -35,10 +42,10 @@ impl From<Cylinder> for Mesh {
         debug_assert!(c.resolution > 2);
         debug_assert!(c.segments > 0);
 
+        // the caps are turned off below, these only count the barrel
         let num_rings = c.segments + 1;
-        let num_vertices = c.resolution * 2 + num_rings * (c.resolution + 1);
-        let num_faces = c.resolution * (num_rings - 2);
-        let num_indices = (2 * num_faces + 2 * (c.resolution - 1) * 2) * 3;
+        let num_vertices = num_rings * (c.resolution + 1);
+        let num_indices = c.segments * c.resolution * 6;
 
         let mut positions = Vec::with_capacity(num_vertices as usize);
         let mut normals = Vec::with_capacity(num_vertices as usize);
@@ -122,6 +129,7 @@ impl From<Cylinder> for Mesh {
         mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
         mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
         mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        c.colors.apply(&mut mesh);
         mesh
     }
 }
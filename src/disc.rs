@@ -0,0 +1,139 @@
+use bevy::{
+    prelude::{Component, Mesh, Reflect, ReflectComponent},
+    render::{mesh::Indices, render_resource::PrimitiveTopology},
+};
+
+/// A flat disc or annulus on the XZ plane facing up, an inner radius of 0 makes a full disc.
+/// UVs are projected top down like [`crate::plane::SubdividedPlane`] so the center of the
+/// disc is the center of the texture.
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component)]
+pub struct Disc {
+    /// Radius of the hole in the middle.
+    pub inner_radius: f32,
+    /// Radius of the outer edge.
+    pub outer_radius: f32,
+    /// Number of vertices around each ring.
+    pub resolution: u32,
+    /// Number of rings between the inner and outer edge.
+    pub rings: u32,
+}
+
+impl Default for Disc {
+    fn default() -> Self {
+        Self {
+            inner_radius: 0.0,
+            outer_radius: 0.5,
+            resolution: 32,
+            rings: 4,
+        }
+    }
+}
+
+impl From<Disc> for Mesh {
+    fn from(d: Disc) -> Self {
+        debug_assert!(d.inner_radius >= 0.0);
+        debug_assert!(d.outer_radius > d.inner_radius);
+        debug_assert!(d.resolution > 2);
+        debug_assert!(d.rings > 0);
+
+        let num_rings = d.rings + 1;
+        let num_vertices = num_rings * (d.resolution + 1);
+        let num_indices = d.rings * d.resolution * 6;
+        let up = [0.0f32, 1.0, 0.0];
+
+        let mut positions = Vec::with_capacity(num_vertices as usize);
+        let mut normals = Vec::with_capacity(num_vertices as usize);
+        let mut uvs = Vec::with_capacity(num_vertices as usize);
+        let mut indices = Vec::with_capacity(num_indices as usize);
+
+        let step_theta = std::f32::consts::TAU / d.resolution as f32;
+        let step_radius = (d.outer_radius - d.inner_radius) / d.rings as f32;
+
+        for ring in 0..num_rings {
+            let radius = d.inner_radius + ring as f32 * step_radius;
+
+            for segment in 0..=d.resolution {
+                let theta = segment as f32 * step_theta;
+                let (sin, cos) = theta.sin_cos();
+                let uv_radius = radius / d.outer_radius;
+
+                positions.push([radius * cos, 0.0, radius * sin]);
+                normals.push(up);
+                uvs.push([0.5 * (1.0 + cos * uv_radius), 0.5 * (1.0 - sin * uv_radius)]);
+            }
+        }
+
+        for i in 0..d.rings {
+            let ring = i * (d.resolution + 1);
+            let next_ring = (i + 1) * (d.resolution + 1);
+            // the innermost ring of a full disc is the center point repeated, skip the triangles without area
+            let ring_is_center = i == 0 && d.inner_radius == 0.0;
+
+            for j in 0..d.resolution {
+                indices.extend_from_slice(&[ring + j, next_ring + j + 1, next_ring + j]);
+                if !ring_is_center {
+                    indices.extend_from_slice(&[ring + j, ring + j + 1, next_ring + j + 1]);
+                }
+            }
+        }
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.set_indices(Some(Indices::U32(indices)));
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        mesh
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::{Mesh, Vec2};
+    use bevy::render::mesh::VertexAttributeValues;
+
+    use super::Disc;
+
+    #[test]
+    fn lies_flat_between_its_radii() {
+        let discs = [
+            Disc::default(),
+            Disc {
+                inner_radius: 0.25,
+                outer_radius: 1.0,
+                resolution: 32,
+                rings: 4,
+            },
+        ];
+        for disc in discs {
+            let (inner, outer) = (disc.inner_radius, disc.outer_radius);
+            let mesh = Mesh::from(disc);
+            let positions = mesh
+                .attribute(Mesh::ATTRIBUTE_POSITION)
+                .and_then(VertexAttributeValues::as_float3)
+                .unwrap();
+            for &[x, y, z] in positions {
+                let radius = Vec2::new(x, z).length();
+                assert_eq!(y, 0.0);
+                assert!(radius >= inner - 1e-6 && radius <= outer + 1e-6);
+            }
+            let normals = mesh
+                .attribute(Mesh::ATTRIBUTE_NORMAL)
+                .and_then(VertexAttributeValues::as_float3)
+                .unwrap();
+            assert!(normals.iter().all(|&normal| normal == [0.0, 1.0, 0.0]));
+        }
+    }
+
+    #[test]
+    fn uvs_stay_inside_the_texture() {
+        let mesh = Mesh::from(Disc::default());
+        let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute(Mesh::ATTRIBUTE_UV_0)
+        else {
+            panic!("expected float2 uvs");
+        };
+        for &[u, v] in uvs {
+            assert!((-1e-6..=1.0 + 1e-6).contains(&u) && (-1e-6..=1.0 + 1e-6).contains(&v));
+        }
+    }
+}
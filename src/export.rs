@@ -0,0 +1,428 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use bevy::{
+    prelude::*,
+    render::{mesh::VertexAttributeValues, render_resource::PrimitiveTopology},
+    utils::{HashMap, HashSet},
+};
+use bevy_inspector_egui::bevy_egui::{egui, EguiContext};
+use bevy_mod_picking::Selection;
+use serde_json::json;
+
+const EXPORT_DIR: &str = "exports";
+
+/// Writes meshes to `exports/` as OBJ and binary glTF for comparing with Blender.
+/// `--export` writes every named mesh, the export window writes the selected ones.
+pub struct ExportPlugin;
+
+impl Plugin for ExportPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(export_window);
+        if std::env::args().any(|arg| arg == "--export") {
+            app.add_system(export_all);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ExportError {
+    Io(io::Error),
+    /// Only indexed or unindexed triangle lists with positions can be exported.
+    Unsupported(&'static str),
+}
+
+impl From<io::Error> for ExportError {
+    fn from(err: io::Error) -> Self {
+        ExportError::Io(err)
+    }
+}
+
+impl std::fmt::Display for ExportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportError::Io(err) => write!(f, "{}", err),
+            ExportError::Unsupported(reason) => write!(f, "unsupported mesh: {}", reason),
+        }
+    }
+}
+
+/// The parts of a mesh both formats store.
+struct ExportMesh<'a> {
+    positions: &'a [[f32; 3]],
+    normals: Option<&'a [[f32; 3]]>,
+    uvs: Option<&'a [[f32; 2]]>,
+    indices: Vec<u32>,
+}
+
+impl<'a> ExportMesh<'a> {
+    fn new(mesh: &'a Mesh) -> Result<Self, ExportError> {
+        if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+            return Err(ExportError::Unsupported("not a triangle list"));
+        }
+        let positions = match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+            Some(VertexAttributeValues::Float32x3(positions)) => positions.as_slice(),
+            _ => return Err(ExportError::Unsupported("no positions")),
+        };
+        let normals = match mesh.attribute(Mesh::ATTRIBUTE_NORMAL) {
+            Some(VertexAttributeValues::Float32x3(normals)) => Some(normals.as_slice()),
+            _ => None,
+        };
+        let uvs = match mesh.attribute(Mesh::ATTRIBUTE_UV_0) {
+            Some(VertexAttributeValues::Float32x2(uvs)) => Some(uvs.as_slice()),
+            _ => None,
+        };
+        let indices = match mesh.indices() {
+            Some(indices) => indices.iter().map(|i| i as u32).collect(),
+            None => (0..positions.len() as u32).collect(),
+        };
+        Ok(ExportMesh {
+            positions,
+            normals,
+            uvs,
+            indices,
+        })
+    }
+}
+
+/// Writes a Wavefront OBJ, flipping V since OBJ puts the UV origin at the bottom left.
+pub fn write_obj(mesh: &Mesh, name: &str, mut writer: impl Write) -> Result<(), ExportError> {
+    let mesh = ExportMesh::new(mesh)?;
+
+    writeln!(writer, "o {}", name)?;
+    for [x, y, z] in mesh.positions {
+        writeln!(writer, "v {} {} {}", x, y, z)?;
+    }
+    for [u, v] in mesh.uvs.unwrap_or_default() {
+        writeln!(writer, "vt {} {}", u, 1.0 - v)?;
+    }
+    for [x, y, z] in mesh.normals.unwrap_or_default() {
+        writeln!(writer, "vn {} {} {}", x, y, z)?;
+    }
+
+    // obj indices start at 1
+    let corner = |i: u32| {
+        let i = i + 1;
+        match (mesh.uvs.is_some(), mesh.normals.is_some()) {
+            (true, true) => format!("{}/{}/{}", i, i, i),
+            (true, false) => format!("{}/{}", i, i),
+            (false, true) => format!("{}//{}", i, i),
+            (false, false) => format!("{}", i),
+        }
+    };
+    for triangle in mesh.indices.chunks_exact(3) {
+        writeln!(
+            writer,
+            "f {} {} {}",
+            corner(triangle[0]),
+            corner(triangle[1]),
+            corner(triangle[2])
+        )?;
+    }
+    Ok(())
+}
+
+// https://registry.khronos.org/glTF/specs/2.0/glTF-2.0.html#binary-gltf-layout
+const GLB_MAGIC: u32 = 0x4654_6C67;
+const GLB_CHUNK_JSON: u32 = 0x4E4F_534A;
+const GLB_CHUNK_BIN: u32 = 0x004E_4942;
+const GLTF_FLOAT: u32 = 5126;
+const GLTF_UNSIGNED_INT: u32 = 5125;
+const GLTF_ARRAY_BUFFER: u32 = 34962;
+const GLTF_ELEMENT_ARRAY_BUFFER: u32 = 34963;
+
+/// Writes a binary glTF with a single node holding the mesh.
+pub fn write_glb(mesh: &Mesh, name: &str, mut writer: impl Write) -> Result<(), ExportError> {
+    let mesh = ExportMesh::new(mesh)?;
+
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut buffer_views = Vec::new();
+    let mut accessors = Vec::new();
+    let mut attributes = serde_json::Map::new();
+
+    let mut push_view = |buffer: &mut Vec<u8>, bytes: &[u8], target: u32| {
+        let offset = buffer.len();
+        buffer.extend_from_slice(bytes);
+        // accessors need 4 byte alignment
+        while buffer.len() % 4 != 0 {
+            buffer.push(0);
+        }
+        buffer_views.push(json!({
+            "buffer": 0,
+            "byteOffset": offset,
+            "byteLength": bytes.len(),
+            "target": target,
+        }));
+        buffer_views.len() - 1
+    };
+
+    let (min, max) = mesh.positions.iter().fold(
+        (Vec3::splat(f32::MAX), Vec3::splat(f32::MIN)),
+        |(min, max), p| (min.min(Vec3::from(*p)), max.max(Vec3::from(*p))),
+    );
+    let view = push_view(
+        &mut buffer,
+        &float_bytes(mesh.positions.iter().flatten()),
+        GLTF_ARRAY_BUFFER,
+    );
+    attributes.insert("POSITION".into(), json!(accessors.len()));
+    accessors.push(json!({
+        "bufferView": view,
+        "componentType": GLTF_FLOAT,
+        "count": mesh.positions.len(),
+        "type": "VEC3",
+        "min": min.to_array(),
+        "max": max.to_array(),
+    }));
+
+    if let Some(normals) = mesh.normals {
+        let view = push_view(
+            &mut buffer,
+            &float_bytes(normals.iter().flatten()),
+            GLTF_ARRAY_BUFFER,
+        );
+        attributes.insert("NORMAL".into(), json!(accessors.len()));
+        accessors.push(json!({
+            "bufferView": view,
+            "componentType": GLTF_FLOAT,
+            "count": normals.len(),
+            "type": "VEC3",
+        }));
+    }
+
+    // gltf and bevy share the top left UV origin
+    if let Some(uvs) = mesh.uvs {
+        let view = push_view(
+            &mut buffer,
+            &float_bytes(uvs.iter().flatten()),
+            GLTF_ARRAY_BUFFER,
+        );
+        attributes.insert("TEXCOORD_0".into(), json!(accessors.len()));
+        accessors.push(json!({
+            "bufferView": view,
+            "componentType": GLTF_FLOAT,
+            "count": uvs.len(),
+            "type": "VEC2",
+        }));
+    }
+
+    let index_bytes: Vec<u8> = mesh.indices.iter().flat_map(|i| i.to_le_bytes()).collect();
+    let view = push_view(&mut buffer, &index_bytes, GLTF_ELEMENT_ARRAY_BUFFER);
+    let indices_accessor = accessors.len();
+    accessors.push(json!({
+        "bufferView": view,
+        "componentType": GLTF_UNSIGNED_INT,
+        "count": mesh.indices.len(),
+        "type": "SCALAR",
+    }));
+
+    let document = json!({
+        "asset": { "version": "2.0", "generator": "fun_shade" },
+        "scene": 0,
+        "scenes": [{ "nodes": [0] }],
+        "nodes": [{ "mesh": 0, "name": name }],
+        "meshes": [{
+            "name": name,
+            "primitives": [{ "attributes": attributes, "indices": indices_accessor }],
+        }],
+        "buffers": [{ "byteLength": buffer.len() }],
+        "bufferViews": buffer_views,
+        "accessors": accessors,
+    });
+
+    let mut json_bytes = serde_json::to_vec(&document).expect("gltf json is always serializable");
+    // chunks are padded to 4 bytes, json with spaces
+    while json_bytes.len() % 4 != 0 {
+        json_bytes.push(b' ');
+    }
+
+    let total_length = 12 + 8 + json_bytes.len() + 8 + buffer.len();
+    writer.write_all(&GLB_MAGIC.to_le_bytes())?;
+    writer.write_all(&2u32.to_le_bytes())?;
+    writer.write_all(&(total_length as u32).to_le_bytes())?;
+    writer.write_all(&(json_bytes.len() as u32).to_le_bytes())?;
+    writer.write_all(&GLB_CHUNK_JSON.to_le_bytes())?;
+    writer.write_all(&json_bytes)?;
+    writer.write_all(&(buffer.len() as u32).to_le_bytes())?;
+    writer.write_all(&GLB_CHUNK_BIN.to_le_bytes())?;
+    writer.write_all(&buffer)?;
+    Ok(())
+}
+
+fn float_bytes<'a>(values: impl Iterator<Item = &'a f32>) -> Vec<u8> {
+    values.flat_map(|v| v.to_le_bytes()).collect()
+}
+
+/// Writes `<file_name>.obj` and `<file_name>.glb` into `dir`, with the mesh called `name` inside them.
+/// Returns the paths written.
+pub fn export_mesh(
+    mesh: &Mesh,
+    name: &str,
+    file_name: &str,
+    dir: impl AsRef<Path>,
+) -> Result<Vec<PathBuf>, ExportError> {
+    let dir = dir.as_ref();
+    std::fs::create_dir_all(dir)?;
+    let file_name: String = file_name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+
+    let obj_path = dir.join(format!("{}.obj", file_name));
+    let mut obj = BufWriter::new(File::create(&obj_path)?);
+    write_obj(mesh, name, &mut obj)?;
+    obj.flush()?;
+
+    let glb_path = dir.join(format!("{}.glb", file_name));
+    let mut glb = BufWriter::new(File::create(&glb_path)?);
+    write_glb(mesh, name, &mut glb)?;
+    glb.flush()?;
+
+    Ok(vec![obj_path, glb_path])
+}
+
+/// How many entities use each name, to tell which names can be file names on their own.
+fn name_counts<'a>(names: impl Iterator<Item = &'a Name>) -> HashMap<&'a str, usize> {
+    let mut counts = HashMap::default();
+    for name in names {
+        *counts.entry(name.as_str()).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Exports an entity's mesh, entities without a name or sharing one with another entity
+/// get their index added to the file name so they don't overwrite each other.
+fn export_entity(
+    mesh: &Mesh,
+    entity: Entity,
+    name: Option<&Name>,
+    name_counts: &HashMap<&str, usize>,
+) {
+    let name = name.map_or("mesh", |name| name.as_str());
+    let file_name = if name_counts.get(name) == Some(&1) {
+        name.to_string()
+    } else {
+        format!("{}_{}", name, entity.index())
+    };
+    match export_mesh(mesh, name, &file_name, EXPORT_DIR) {
+        Ok(paths) => {
+            for path in paths {
+                info!("exported {}", path.display());
+            }
+        }
+        Err(err) => warn!("could not export {}: {}", file_name, err),
+    }
+}
+
+fn export_window(
+    mut egui_context: ResMut<EguiContext>,
+    meshes: Res<Assets<Mesh>>,
+    query: Query<(Entity, &Selection, &Handle<Mesh>, Option<&Name>)>,
+) {
+    egui::Window::new("Export").show(egui_context.ctx_mut(), |ui| {
+        let selected: Vec<_> = query.iter().filter(|(_, s, _, _)| s.selected()).collect();
+        ui.label(format!("{} selected", selected.len()));
+        if ui.button("Export selected to OBJ and GLB").clicked() {
+            let counts = name_counts(query.iter().filter_map(|(_, _, _, name)| name));
+            for (entity, _, handle, name) in selected {
+                if let Some(mesh) = meshes.get(handle) {
+                    export_entity(mesh, entity, name, &counts);
+                }
+            }
+        }
+    });
+}
+
+fn export_all(
+    meshes: Res<Assets<Mesh>>,
+    query: Query<(Entity, &Handle<Mesh>, &Name)>,
+    mut exported: Local<HashSet<Entity>>,
+) {
+    let counts = name_counts(query.iter().map(|(_, _, name)| name));
+    // meshes spawn as their assets load, so keep checking for new ones
+    for (entity, handle, name) in query.iter() {
+        if exported.contains(&entity) {
+            continue;
+        }
+        if let Some(mesh) = meshes.get(handle) {
+            export_entity(mesh, entity, Some(name), &counts);
+            exported.insert(entity);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::{
+        asset::LoadState,
+        gltf::GltfPlugin,
+        prelude::*,
+        render::mesh::{MeshVertexAttribute, VertexAttributeValues},
+    };
+
+    use super::export_mesh;
+    use crate::capsule::Capsule;
+
+    fn values<'a>(mesh: &'a Mesh, attribute: &MeshVertexAttribute) -> &'a [u8] {
+        mesh.attribute(attribute.id)
+            .map(VertexAttributeValues::get_bytes)
+            .unwrap_or_else(|| panic!("mesh has no {}", attribute.name))
+    }
+
+    #[test]
+    fn glb_round_trip() {
+        let dir = std::env::temp_dir().join(format!("fun_shade_export_{}", std::process::id()));
+        let mesh: Mesh = Capsule::default().into();
+        export_mesh(&mesh, "capsule", "capsule", &dir).unwrap();
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugin(AssetPlugin {
+                asset_folder: dir.to_string_lossy().into_owned(),
+                watch_for_changes: false,
+            })
+            .add_asset::<Mesh>()
+            .add_asset::<Image>()
+            .add_asset::<StandardMaterial>()
+            .add_asset::<Scene>()
+            .add_plugin(GltfPlugin);
+        let handle: Handle<Mesh> = app
+            .world
+            .resource::<AssetServer>()
+            .load("capsule.glb#Mesh0/Primitive0");
+
+        // the loader runs on the io task pool, the mesh shows up a few updates later
+        for _ in 0..500 {
+            app.update();
+            if app.world.resource::<Assets<Mesh>>().contains(&handle) {
+                break;
+            }
+            let state = app.world.resource::<AssetServer>().get_load_state(&handle);
+            assert_ne!(state, LoadState::Failed, "the exported glb didn't load");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        std::fs::remove_dir_all(&dir).ok();
+
+        let meshes = app.world.resource::<Assets<Mesh>>();
+        let loaded = meshes.get(&handle).expect("the exported glb has no mesh");
+        for attribute in [
+            Mesh::ATTRIBUTE_POSITION,
+            Mesh::ATTRIBUTE_NORMAL,
+            Mesh::ATTRIBUTE_UV_0,
+        ] {
+            assert_eq!(
+                values(loaded, &attribute),
+                values(&mesh, &attribute),
+                "{} changed",
+                attribute.name
+            );
+        }
+        let indices = |mesh: &Mesh| mesh.indices().unwrap().iter().collect::<Vec<_>>();
+        assert_eq!(indices(loaded), indices(&mesh));
+    }
+}
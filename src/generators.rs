@@ -0,0 +1,153 @@
+use bevy::prelude::*;
+use bevy::reflect::GetTypeRegistration;
+
+use crate::capsule::Capsule;
+use crate::cone::Cone;
+use crate::cube_sphere::CubeSphere;
+use crate::cylinder::Cylinder;
+use crate::disc::Disc;
+use crate::icosphere::Icosphere;
+use crate::lod::MeshLod;
+use crate::plane::SubdividedPlane;
+use crate::tube::Tube;
+use crate::vertex_colors::VertexColors;
+
+/// Mesh generators as components, editing one in the inspector rebuilds the entity's mesh.
+/// An entity spawned with a generator and `Handle::default()` gets its mesh built for it.
+pub struct GeneratorPlugin;
+
+impl Plugin for GeneratorPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<VertexColors>()
+            .add_generator::<SubdividedPlane>()
+            .add_generator::<Cylinder>()
+            .add_generator::<Cone>()
+            .add_generator::<Disc>()
+            .add_generator::<Tube>()
+            .add_generator::<Capsule>()
+            .add_generator::<Icosphere>()
+            .add_generator::<CubeSphere>();
+    }
+}
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
+pub struct RegenerateMeshes;
+
+pub trait AddGenerator {
+    fn add_generator<T: MeshGenerator>(&mut self) -> &mut Self;
+}
+
+impl AddGenerator for App {
+    fn add_generator<T: MeshGenerator>(&mut self) -> &mut Self {
+        self.register_type::<T>()
+            .add_system(regenerate_mesh::<T>.label(RegenerateMeshes))
+    }
+}
+
+/// A component that can build a mesh from its fields.
+pub trait MeshGenerator: Component + Reflect + GetTypeRegistration + Clone + Into<Mesh> {
+    /// The generators `debug_assert!` on bad input, the inspector can set anything,
+    /// so only rebuild from values the generator accepts.
+    fn is_valid(&self) -> bool;
+}
+
+impl MeshGenerator for SubdividedPlane {
+    fn is_valid(&self) -> bool {
+        // about a million vertices, past this an edit stalls while it allocates
+        self.size > 0.0 && self.subdivisions <= 1024
+    }
+}
+
+/// A generator that can build coarser copies of itself for the levels of a [`MeshLod`].
+pub trait LodGenerator: MeshGenerator {
+    /// A copy with about `detail` times the vertices along each edge, at least one quad wide.
+    fn with_detail(&self, detail: f32) -> Self;
+}
+
+impl LodGenerator for SubdividedPlane {
+    fn with_detail(&self, detail: f32) -> Self {
+        let quads = ((self.subdivisions + 1) as f32 * detail).round().max(1.0) as u32;
+        Self {
+            subdivisions: quads - 1,
+            ..self.clone()
+        }
+    }
+}
+
+impl MeshGenerator for Cylinder {
+    fn is_valid(&self) -> bool {
+        self.radius > 0.0 && self.height > 0.0 && self.resolution > 2 && self.segments > 0
+    }
+}
+
+impl MeshGenerator for Cone {
+    fn is_valid(&self) -> bool {
+        self.bottom_radius >= 0.0
+            && self.top_radius >= 0.0
+            && (self.bottom_radius > 0.0 || self.top_radius > 0.0)
+            && self.height > 0.0
+            && self.resolution > 2
+            && self.segments > 0
+    }
+}
+
+impl MeshGenerator for Disc {
+    fn is_valid(&self) -> bool {
+        self.inner_radius >= 0.0
+            && self.outer_radius > self.inner_radius
+            && self.resolution > 2
+            && self.rings > 0
+    }
+}
+
+impl MeshGenerator for Tube {
+    fn is_valid(&self) -> bool {
+        self.radius > 0.0
+            && self.thickness > 0.0
+            && self.thickness < self.radius
+            && self.height > 0.0
+            && self.resolution > 2
+            && self.segments > 0
+    }
+}
+
+impl MeshGenerator for Capsule {
+    fn is_valid(&self) -> bool {
+        self.radius > 0.0
+            && self.depth >= 0.0
+            && self.resolution > 2
+            && self.rings > 0
+            && self.segments > 0
+    }
+}
+
+impl MeshGenerator for Icosphere {
+    fn is_valid(&self) -> bool {
+        // every level is four times the triangles, past this it takes seconds
+        self.radius > 0.0 && self.subdivisions <= 7
+    }
+}
+
+impl MeshGenerator for CubeSphere {
+    fn is_valid(&self) -> bool {
+        // six faces of a plane, this keeps it around a million and a half vertices
+        self.radius > 0.0 && self.subdivisions <= 512
+    }
+}
+
+/// Entities with a [`MeshLod`] get their meshes from the [`crate::lod::LodPlugin`] instead.
+fn regenerate_mesh<T: MeshGenerator>(
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut query: Query<(&T, &mut Handle<Mesh>, ChangeTrackers<T>), (Changed<T>, Without<MeshLod>)>,
+) {
+    for (generator, mut handle, tracker) in query.iter_mut() {
+        // spawned with its mesh already built
+        if tracker.is_added() && *handle != Handle::default() {
+            continue;
+        }
+        if !generator.is_valid() {
+            continue;
+        }
+        *handle = meshes.add(generator.clone().into());
+    }
+}
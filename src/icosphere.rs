@@ -0,0 +1,139 @@
+use bevy::{
+    prelude::{Component, Mesh, Reflect, ReflectComponent, Vec3},
+    render::{mesh::Indices, render_resource::PrimitiveTopology},
+    utils::HashMap,
+};
+
+/// A sphere made by subdividing an icosahedron, the vertices are spread evenly
+/// instead of bunching up at the poles like [`bevy::prelude::shape::UVSphere`].
+/// Vertices on the UV seam and at the poles are split so the texture doesn't smear.
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component)]
+pub struct Icosphere {
+    pub radius: f32,
+    /// Each level splits every triangle into four.
+    pub subdivisions: u32,
+}
+
+impl Default for Icosphere {
+    fn default() -> Self {
+        Self {
+            radius: 0.5,
+            subdivisions: 3,
+        }
+    }
+}
+
+impl From<Icosphere> for Mesh {
+    fn from(sphere: Icosphere) -> Self {
+        debug_assert!(sphere.radius > 0.0);
+
+        let phi = (1.0 + 5.0f32.sqrt()) / 2.0;
+        let mut points: Vec<Vec3> = [
+            [-1.0, phi, 0.0],
+            [1.0, phi, 0.0],
+            [-1.0, -phi, 0.0],
+            [1.0, -phi, 0.0],
+            [0.0, -1.0, phi],
+            [0.0, 1.0, phi],
+            [0.0, -1.0, -phi],
+            [0.0, 1.0, -phi],
+            [phi, 0.0, -1.0],
+            [phi, 0.0, 1.0],
+            [-phi, 0.0, -1.0],
+            [-phi, 0.0, 1.0],
+        ]
+        .iter()
+        .map(|p| Vec3::from(*p).normalize())
+        .collect();
+
+        let mut indices: Vec<u32> = vec![
+            0, 11, 5, 0, 5, 1, 0, 1, 7, 0, 7, 10, 0, 10, 11, //
+            1, 5, 9, 5, 11, 4, 11, 10, 2, 10, 7, 6, 7, 1, 8, //
+            3, 9, 4, 3, 4, 2, 3, 2, 6, 3, 6, 8, 3, 8, 9, //
+            4, 9, 5, 2, 4, 11, 6, 2, 10, 8, 6, 7, 9, 8, 1,
+        ];
+
+        for _ in 0..sphere.subdivisions {
+            let mut midpoints: HashMap<(u32, u32), u32> = HashMap::default();
+            let mut midpoint = |a: u32, b: u32, points: &mut Vec<Vec3>| {
+                let key = (a.min(b), a.max(b));
+                *midpoints.entry(key).or_insert_with(|| {
+                    let point = ((points[a as usize] + points[b as usize]) / 2.0).normalize();
+                    points.push(point);
+                    points.len() as u32 - 1
+                })
+            };
+
+            let mut next = Vec::with_capacity(indices.len() * 4);
+            for triangle in indices.chunks_exact(3) {
+                let (a, b, c) = (triangle[0], triangle[1], triangle[2]);
+                let ab = midpoint(a, b, &mut points);
+                let bc = midpoint(b, c, &mut points);
+                let ca = midpoint(c, a, &mut points);
+                next.extend_from_slice(&[a, ab, ca, b, bc, ab, c, ca, bc, ab, bc, ca]);
+            }
+            indices = next;
+        }
+
+        let uv = |p: Vec3| {
+            [
+                0.5 + p.z.atan2(p.x) / std::f32::consts::TAU,
+                p.y.clamp(-1.0, 1.0).acos() / std::f32::consts::PI,
+            ]
+        };
+        let mut uvs: Vec<[f32; 2]> = points.iter().map(|p| uv(*p)).collect();
+        let is_pole = |p: Vec3| p.x.abs() < 1e-6 && p.z.abs() < 1e-6;
+
+        // triangles across the seam get copies of their low side vertices with u past 1,
+        // triangles touching a pole get their own pole vertex with u between their other corners
+        let mut wrapped: HashMap<u32, u32> = HashMap::default();
+        for triangle in indices.chunks_exact_mut(3) {
+            let us: Vec<f32> = triangle
+                .iter()
+                .filter(|&&i| !is_pole(points[i as usize]))
+                .map(|&i| uvs[i as usize][0])
+                .collect();
+            let min = us.iter().cloned().fold(f32::MAX, f32::min);
+            let max = us.iter().cloned().fold(f32::MIN, f32::max);
+
+            if max - min > 0.5 {
+                for index in triangle.iter_mut() {
+                    let i = *index as usize;
+                    if !is_pole(points[i]) && uvs[i][0] < 0.5 {
+                        *index = *wrapped.entry(*index).or_insert_with(|| {
+                            points.push(points[i]);
+                            uvs.push([uvs[i][0] + 1.0, uvs[i][1]]);
+                            points.len() as u32 - 1
+                        });
+                    }
+                }
+            }
+
+            for corner in 0..3 {
+                let i = triangle[corner] as usize;
+                if is_pole(points[i]) {
+                    let u = (uvs[triangle[(corner + 1) % 3] as usize][0]
+                        + uvs[triangle[(corner + 2) % 3] as usize][0])
+                        / 2.0;
+                    points.push(points[i]);
+                    uvs.push([u, uvs[i][1]]);
+                    triangle[corner] = points.len() as u32 - 1;
+                }
+            }
+        }
+
+        let positions: Vec<[f32; 3]> = points
+            .iter()
+            .map(|p| (*p * sphere.radius).to_array())
+            .collect();
+        let normals: Vec<[f32; 3]> = points.iter().map(|p| p.to_array()).collect();
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.set_indices(Some(Indices::U32(indices)));
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        mesh
+    }
+}
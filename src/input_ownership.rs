@@ -0,0 +1,262 @@
+use bevy::ecs::schedule::ShouldRun;
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use bevy_inspector_egui::bevy_egui::EguiContext;
+use bevy_mod_picking::PickingPluginsState;
+use bevy_transform_gizmo::GizmoPickSource;
+
+use crate::camera::orbit_pressed;
+
+/// Decides which part of the app handles the pointer, so a drag that starts on
+/// the gizmo doesn't also orbit the camera and scrolling over egui doesn't zoom.
+pub struct InputOwnershipPlugin;
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
+pub struct InputOwnershipSystem;
+
+impl Plugin for InputOwnershipPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputOwnership>().add_system_to_stage(
+            CoreStage::PreUpdate,
+            arbitrate_input
+                .label(InputOwnershipSystem)
+                .after(InputSystem),
+        );
+    }
+}
+
+/// The consumers competing for the pointer, in order of priority.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PointerConsumer {
+    #[default]
+    None,
+    Egui,
+    Gizmo,
+    /// Only owns drags, a left click on the scene that isn't an orbit.
+    Picking,
+    Camera,
+}
+
+#[derive(Resource, Default, Debug)]
+pub struct InputOwnership {
+    /// Owner of the drag in progress, kept until every button and finger is released.
+    pub drag: PointerConsumer,
+    /// Egui, the gizmo or, over the rest of the scene, the camera, which also owns the scroll wheel.
+    pub hover: PointerConsumer,
+}
+
+/// Where the pointer is and what is pressed, everything the ownership is decided from.
+#[derive(Clone, Copy, Debug)]
+struct PointerState {
+    egui_hovered: bool,
+    gizmo_hovered: bool,
+    any_pressed: bool,
+    left_pressed: bool,
+    orbit_pressed: bool,
+    touching: bool,
+}
+
+impl InputOwnership {
+    /// The consumer that should handle pointer input this frame.
+    pub fn owner(&self) -> PointerConsumer {
+        if self.drag != PointerConsumer::None {
+            self.drag
+        } else {
+            self.hover
+        }
+    }
+
+    pub fn is_owned_by(&self, consumer: PointerConsumer) -> bool {
+        self.owner() == consumer
+    }
+
+    /// Whether picking may hover and select, during its own drags and while the pointer is
+    /// over the scene with nothing pressed. Clicks on egui, the gizmo or an orbit drag
+    /// shouldn't change the selection.
+    pub fn picking_enabled(&self) -> bool {
+        match self.drag {
+            PointerConsumer::None => self.hover == PointerConsumer::Camera,
+            drag => drag == PointerConsumer::Picking,
+        }
+    }
+
+    fn update(&mut self, pointer: PointerState) {
+        self.hover = if pointer.egui_hovered {
+            PointerConsumer::Egui
+        } else if pointer.gizmo_hovered {
+            PointerConsumer::Gizmo
+        } else {
+            PointerConsumer::Camera
+        };
+
+        if !pointer.any_pressed && !pointer.touching {
+            self.drag = PointerConsumer::None;
+        } else if self.drag == PointerConsumer::None {
+            // a drag has started, hand it to exactly one consumer
+            self.drag = if pointer.egui_hovered {
+                PointerConsumer::Egui
+            } else if pointer.gizmo_hovered && pointer.left_pressed {
+                PointerConsumer::Gizmo
+            } else if pointer.orbit_pressed || pointer.touching {
+                PointerConsumer::Camera
+            } else {
+                PointerConsumer::Picking
+            };
+        }
+    }
+}
+
+/// Run criteria for systems that should only run while `consumer` has the pointer.
+pub fn pointer_owned_by(consumer: PointerConsumer) -> impl FnMut(Res<InputOwnership>) -> ShouldRun {
+    move |ownership: Res<InputOwnership>| {
+        if ownership.is_owned_by(consumer) {
+            ShouldRun::Yes
+        } else {
+            ShouldRun::No
+        }
+    }
+}
+
+fn arbitrate_input(
+    mut ownership: ResMut<InputOwnership>,
+    mut egui_context: ResMut<EguiContext>,
+    input_mouse: Res<Input<MouseButton>>,
+    keyboard_input: Res<Input<KeyCode>>,
+    touches: Res<Touches>,
+    gizmo_sources: Query<&GizmoPickSource>,
+    picking_state: Option<ResMut<PickingPluginsState>>,
+) {
+    let ctx = egui_context.ctx_mut();
+    ownership.update(PointerState {
+        egui_hovered: ctx.wants_pointer_input()
+            || ctx.is_pointer_over_area()
+            || ctx.is_using_pointer(),
+        gizmo_hovered: gizmo_sources
+            .iter()
+            .any(|source| source.intersect_top().is_some()),
+        any_pressed: input_mouse.get_pressed().next().is_some(),
+        left_pressed: input_mouse.pressed(MouseButton::Left),
+        orbit_pressed: orbit_pressed(&input_mouse, &keyboard_input),
+        touching: touches.iter().next().is_some(),
+    });
+
+    if let Some(mut picking_state) = picking_state {
+        let picking = ownership.picking_enabled();
+        if picking_state.enable_picking != picking {
+            picking_state.enable_picking = picking;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InputOwnership, PointerConsumer, PointerState};
+
+    const OVER_SCENE: PointerState = PointerState {
+        egui_hovered: false,
+        gizmo_hovered: false,
+        any_pressed: false,
+        left_pressed: false,
+        orbit_pressed: false,
+        touching: false,
+    };
+    const LEFT: PointerState = PointerState {
+        any_pressed: true,
+        left_pressed: true,
+        ..OVER_SCENE
+    };
+    const ORBIT: PointerState = PointerState {
+        any_pressed: true,
+        orbit_pressed: true,
+        ..OVER_SCENE
+    };
+
+    /// Runs the frames in order, returns the owner and whether picking is on after the last.
+    fn decide(frames: &[PointerState]) -> (PointerConsumer, bool) {
+        let mut ownership = InputOwnership::default();
+        for &frame in frames {
+            ownership.update(frame);
+        }
+        (ownership.owner(), ownership.picking_enabled())
+    }
+
+    #[test]
+    fn hovering() {
+        assert_eq!(decide(&[OVER_SCENE]), (PointerConsumer::Camera, true));
+        let over_egui = PointerState {
+            egui_hovered: true,
+            gizmo_hovered: true,
+            ..OVER_SCENE
+        };
+        assert_eq!(decide(&[over_egui]), (PointerConsumer::Egui, false));
+        let over_gizmo = PointerState {
+            gizmo_hovered: true,
+            ..OVER_SCENE
+        };
+        assert_eq!(decide(&[over_gizmo]), (PointerConsumer::Gizmo, false));
+    }
+
+    #[test]
+    fn starting_a_drag() {
+        assert_eq!(decide(&[LEFT]), (PointerConsumer::Picking, true));
+        assert_eq!(decide(&[ORBIT]), (PointerConsumer::Camera, false));
+        let alt_left = PointerState {
+            orbit_pressed: true,
+            ..LEFT
+        };
+        assert_eq!(decide(&[alt_left]), (PointerConsumer::Camera, false));
+        let touch = PointerState {
+            touching: true,
+            ..OVER_SCENE
+        };
+        assert_eq!(decide(&[touch]), (PointerConsumer::Camera, false));
+        let gizmo_left = PointerState {
+            gizmo_hovered: true,
+            ..LEFT
+        };
+        assert_eq!(decide(&[gizmo_left]), (PointerConsumer::Gizmo, false));
+        // only the left button drags the gizmo
+        let gizmo_orbit = PointerState {
+            gizmo_hovered: true,
+            ..ORBIT
+        };
+        assert_eq!(decide(&[gizmo_orbit]), (PointerConsumer::Camera, false));
+        let egui_left = PointerState {
+            egui_hovered: true,
+            ..LEFT
+        };
+        assert_eq!(decide(&[egui_left]), (PointerConsumer::Egui, false));
+    }
+
+    #[test]
+    fn drags_keep_their_owner_until_released() {
+        let orbit_onto_egui = PointerState {
+            egui_hovered: true,
+            ..ORBIT
+        };
+        assert_eq!(
+            decide(&[ORBIT, orbit_onto_egui]),
+            (PointerConsumer::Camera, false)
+        );
+        let pick_onto_gizmo = PointerState {
+            gizmo_hovered: true,
+            ..LEFT
+        };
+        assert_eq!(
+            decide(&[LEFT, pick_onto_gizmo]),
+            (PointerConsumer::Picking, true)
+        );
+        let released_over_egui = PointerState {
+            egui_hovered: true,
+            ..OVER_SCENE
+        };
+        assert_eq!(
+            decide(&[ORBIT, released_over_egui]),
+            (PointerConsumer::Egui, false)
+        );
+        assert_eq!(
+            decide(&[ORBIT, OVER_SCENE]),
+            (PointerConsumer::Camera, true)
+        );
+    }
+}
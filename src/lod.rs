@@ -0,0 +1,154 @@
+use bevy::{prelude::*, transform::TransformSystem};
+#[cfg(debug_assertions)]
+use bevy_inspector_egui::bevy_egui::{egui, EguiContext};
+
+use crate::camera::PanOrbitCamera;
+use crate::generators::{LodGenerator, RegenerateMeshes};
+use crate::plane::SubdividedPlane;
+
+/// Swaps an entity's mesh for a coarser one as the cameras move away from it.
+pub struct LodPlugin;
+
+impl Plugin for LodPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<MeshLod>()
+            .register_type::<LodLevel>()
+            .add_system(regenerate_lod::<SubdividedPlane>.label(RegenerateMeshes))
+            // the camera distance has to come from this frame's transforms
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                switch_lod.after(TransformSystem::TransformPropagate),
+            );
+
+        #[cfg(debug_assertions)]
+        app.add_system(lod_indicator);
+    }
+}
+
+/// One mesh of a [`MeshLod`], used while the camera is closer than `max_distance`.
+#[derive(Reflect, FromReflect, Clone, Debug)]
+pub struct LodLevel {
+    pub max_distance: f32,
+    /// Passed to [`LodGenerator::with_detail`], 1.0 builds the generator as it is.
+    pub detail: f32,
+    pub mesh: Handle<Mesh>,
+}
+
+/// Meshes from the most to the least detailed, the entity's `Handle<Mesh>` is set
+/// to the level for the distance to the closest [`PanOrbitCamera`].
+/// The last level is used past every `max_distance`.
+/// A [`crate::shading::Shading`] on the same entity is ignored, both want to pick the mesh.
+#[derive(Component, Reflect, Default, Debug)]
+#[reflect(Component)]
+pub struct MeshLod {
+    pub levels: Vec<LodLevel>,
+    /// Fraction of a level's distance the camera has to move past it before switching,
+    /// so sitting on the boundary doesn't flicker between two meshes.
+    pub hysteresis: f32,
+    /// Index into `levels` of the mesh being shown.
+    pub current: usize,
+    /// Distance to the closest camera at the last update, for the inspector.
+    pub distance: f32,
+}
+
+impl MeshLod {
+    /// Levels built from the entity's [`LodGenerator`] component, `levels` is `(max_distance, detail)`
+    /// from most to least detail. The meshes are rebuilt whenever the generator is edited.
+    pub fn from_generator(levels: impl IntoIterator<Item = (f32, f32)>) -> Self {
+        Self {
+            levels: levels
+                .into_iter()
+                .map(|(max_distance, detail)| LodLevel {
+                    max_distance,
+                    detail,
+                    mesh: Handle::default(),
+                })
+                .collect(),
+            hysteresis: 0.1,
+            ..default()
+        }
+    }
+
+    /// The level to show at `distance`, starting from the current one and only
+    /// moving when `distance` is outside the current level's band widened by the hysteresis.
+    fn level_for(&self, distance: f32) -> usize {
+        let last = self.levels.len().saturating_sub(1);
+        let mut level = self.current.min(last);
+
+        while level < last && distance > self.levels[level].max_distance * (1.0 + self.hysteresis) {
+            level += 1;
+        }
+        while level > 0 && distance < self.levels[level - 1].max_distance * (1.0 - self.hysteresis)
+        {
+            level -= 1;
+        }
+        level
+    }
+}
+
+/// Builds every level's mesh from the generator, editing the meshes in place keeps
+/// the handles the same so [`switch_lod`] and anything copying them don't have to notice.
+fn regenerate_lod<T: LodGenerator>(
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut query: Query<(&T, &mut MeshLod), Changed<T>>,
+) {
+    for (generator, mut lod) in query.iter_mut() {
+        if !generator.is_valid() {
+            continue;
+        }
+        for level in lod.levels.iter_mut() {
+            let mesh = generator.with_detail(level.detail).into();
+            match meshes.get_mut(&level.mesh) {
+                Some(existing) => *existing = mesh,
+                None => level.mesh = meshes.add(mesh),
+            }
+        }
+    }
+}
+
+fn switch_lod(
+    cameras: Query<&GlobalTransform, With<PanOrbitCamera>>,
+    mut query: Query<(&mut MeshLod, &mut Handle<Mesh>, &GlobalTransform)>,
+) {
+    for (mut lod, mut handle, transform) in query.iter_mut() {
+        if lod.levels.is_empty() {
+            continue;
+        }
+        let Some(distance) = cameras
+            .iter()
+            .map(|camera| camera.translation().distance(transform.translation()))
+            .reduce(f32::min)
+        else {
+            continue;
+        };
+
+        let level = lod.level_for(distance);
+        // only write through the change detection when something changed
+        if lod.current != level || lod.distance != distance {
+            lod.distance = distance;
+            lod.current = level;
+        }
+        if *handle != lod.levels[level].mesh {
+            *handle = lod.levels[level].mesh.clone();
+        }
+    }
+}
+
+#[cfg(debug_assertions)]
+fn lod_indicator(mut egui_context: ResMut<EguiContext>, query: Query<(&MeshLod, Option<&Name>)>) {
+    if query.is_empty() {
+        return;
+    }
+    egui::Window::new("LOD").show(egui_context.ctx_mut(), |ui| {
+        for (lod, name) in query.iter() {
+            let name = name.map_or("unnamed", |name| name.as_str());
+            ui.label(format!(
+                "{}: level {} of {} at {:.1}",
+                name,
+                lod.current,
+                lod.levels.len(),
+                lod.distance
+            ));
+        }
+    });
+}
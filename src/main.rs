@@ -1,29 +1,77 @@
 use bevy::{
+    core_pipeline::clear_color::ClearColorConfig,
+    ecs::schedule::ShouldRun,
     gltf::GltfMesh,
     pbr::wireframe::{Wireframe, WireframeConfig, WireframePlugin},
     prelude::*,
     render::{
-        render_resource::{AddressMode, SamplerDescriptor},
+        render_resource::{
+            AddressMode, Extent3d, FilterMode, SamplerDescriptor, TextureDimension,
+            TextureFormat,
+        },
         settings::{WgpuFeatures, WgpuSettings},
         texture::ImageSampler, camera::ScalingMode,
     },
     window::PresentMode,
 };
 
+mod bookmarks;
 mod camera;
+mod camera_path;
+mod capsule;
+mod cone;
+mod cube_sphere;
 mod cylinder;
+mod disc;
+mod export;
+mod generators;
+mod icosphere;
+mod input_ownership;
+mod lod;
 mod materials;
+mod mesh_validation;
+mod parametric;
 mod plane;
+mod shader_check;
+mod shader_errors;
+mod shading;
+mod terrain;
+mod toon;
+mod tube;
+mod vertex_colors;
 
-use camera::{CameraPlugin, PanOrbitCamera};
+use bookmarks::BookmarkPlugin;
+use camera::{CameraPlugin, OrbitViewport, PanOrbitCamera};
+use camera_path::{CameraKeyframe, CameraPath, CameraPathPlugin, PathEasing, PathLooping};
+use capsule::Capsule;
+use cone::Cone;
+use cube_sphere::CubeSphere;
 use cylinder::Cylinder;
+use disc::Disc;
+use export::ExportPlugin;
+use generators::GeneratorPlugin;
+use icosphere::Icosphere;
+use input_ownership::InputOwnershipPlugin;
+use lod::{LodPlugin, MeshLod};
+use mesh_validation::MeshStatsPlugin;
+use parametric::ParametricSurface;
 use plane::SubdividedPlane;
+use shader_errors::ShaderErrorPlugin;
+use shading::{Shading, ShadingPlugin, ShadingStyle};
+use terrain::{Terrain, TerrainPlugin};
+use toon::ToonPlugin;
+use tube::Tube;
+use vertex_colors::VertexColors;
 
 use bevy_inspector_egui::quick::WorldInspectorPlugin;
 use bevy_mod_picking::{DefaultPickingPlugins, PickableBundle, PickingCameraBundle};
 use materials::{CoolMaterial, GeometryMaterial, HealthBarMaterial, WorldSpaceMaterial};
 
-use crate::materials::{GLSLMaterial, MovingTextureMaterial};
+use crate::materials::{
+    DissolveMaterial, FlagMaterial, FlowMaterial, GLSLMaterial, GlslShaderPlugin, HologramMaterial,
+    MaterialsPlugin, MovingTextureMaterial, RimMaterial, ShaderLanguage, ToonMaterial,
+    VertexColorMaterial,
+};
 
 pub const CLEAR: Color = Color::rgb(0.3, 0.3, 0.3);
 pub const HEIGHT: f32 = 900.0;
@@ -39,7 +87,16 @@ struct AppAssets {
     health_image: Handle<Image>,
 }
 
+/// Burns away and comes back over and over, for showing off the [`DissolveMaterial`].
+#[derive(Component)]
+struct CycleDissolve;
+
 fn main() {
+    // `--check-shaders` validates the material shaders and exits instead of opening the window
+    if std::env::args().any(|arg| arg == "--check-shaders") {
+        shader_check::check_shaders();
+    }
+
     let width = 1290.0;
     let height = 700.0;
 
@@ -67,26 +124,44 @@ fn main() {
             ..default()
         })
         .add_plugin(WireframePlugin)
+        .add_plugin(ShaderErrorPlugin)
         .insert_resource(ClearColor(CLEAR))
         .insert_resource(Msaa { samples: 4 })
-        .add_plugin(MaterialPlugin::<CoolMaterial>::default())
-        .add_plugin(MaterialPlugin::<GeometryMaterial>::default())
-        .add_plugin(MaterialPlugin::<GLSLMaterial>::default())
-        .add_plugin(MaterialPlugin::<MovingTextureMaterial>::default())
-        .add_plugin(MaterialPlugin::<WorldSpaceMaterial>::default())
-        .add_plugin(MaterialPlugin::<HealthBarMaterial>::default())
+        .add_plugin(MaterialsPlugin)
+        .add_plugin(GlslShaderPlugin)
         .add_plugin(WorldInspectorPlugin)
+        .add_plugin(ToonPlugin)
+        .add_plugin(InputOwnershipPlugin)
         .add_plugin(CameraPlugin::default())
+        .add_plugin(BookmarkPlugin)
+        .add_plugin(CameraPathPlugin)
+        .add_plugin(GeneratorPlugin)
+        .add_plugin(LodPlugin)
+        .add_plugin(ShadingPlugin)
+        .add_plugin(TerrainPlugin)
+        .add_plugin(MeshStatsPlugin)
+        .add_plugin(ExportPlugin)
         .add_plugins(DefaultPickingPlugins)
         .add_plugin(bevy_transform_gizmo::TransformGizmoPlugin)
         .add_startup_system(spawn_camera)
         .add_startup_system_to_stage(StartupStage::PreStartup, load_assets)
         .add_startup_system_to_stage(StartupStage::Startup, setup)
+        .add_startup_system(setup_segment_count.with_run_criteria(segments_requested))
         .add_system(check_load)
         .add_system(cycle_health)
+        .add_system(cycle_dissolve)
         .run();
 }
 
+/// `--segments` adds the tessellation comparison.
+fn segments_requested() -> ShouldRun {
+    if std::env::args().any(|arg| arg == "--segments") {
+        ShouldRun::Yes
+    } else {
+        ShouldRun::No
+    }
+}
+
 fn load_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
     let gltf_plane = asset_server.load("plane.gltf#Mesh0");
     let map_image = asset_server.load("images/map.png");
@@ -112,6 +187,7 @@ fn check_load(
     mut mesh_assets: ResMut<Assets<Mesh>>,
     mut images: ResMut<Assets<Image>>,
     mut health_materials: ResMut<Assets<HealthBarMaterial>>,
+    mut flow_materials: ResMut<Assets<FlowMaterial>>,
 ) {
     use bevy::asset::LoadState;
 
@@ -139,24 +215,118 @@ fn check_load(
 
         commands.spawn((
             MaterialMeshBundle {
-                mesh: mesh_assets.add(Mesh::from(SubdividedPlane {
-                    subdivisions: 2,
-                    size: plane_size,
-                })),
+                mesh: Handle::default(),
                 material: jam_materials.add(MovingTextureMaterial {
                     color_texture: app_assets.map_image.clone(),
                     pattern: app_assets.pattern_image.clone(),
                     rock: app_assets.rock_image.clone(),
+                    language: ShaderLanguage::Wgsl,
                 }),
                 transform: Transform::from_xyz(0.8, 0.0, 2.0),
                 ..default()
             },
             Name::from("moving texture"),
+            SubdividedPlane {
+                subdivisions: 2,
+                size: plane_size,
+                ..default()
+            },
             PickableBundle::default(),
             bevy_transform_gizmo::GizmoTransformable,
             Wireframe,
         ));
 
+        // `--glsl` puts the GLSL version of each shader above its WGSL twin
+        if std::env::args().any(|arg| arg == "--glsl") {
+            commands.spawn((
+                MaterialMeshBundle {
+                    mesh: Handle::default(),
+                    material: jam_materials.add(MovingTextureMaterial {
+                        color_texture: app_assets.map_image.clone(),
+                        pattern: app_assets.pattern_image.clone(),
+                        rock: app_assets.rock_image.clone(),
+                        language: ShaderLanguage::Glsl,
+                    }),
+                    transform: Transform::from_xyz(0.8, 2.5, 2.0),
+                    ..default()
+                },
+                Name::from("moving texture glsl"),
+                SubdividedPlane {
+                    subdivisions: 2,
+                    size: plane_size,
+                    ..default()
+                },
+                PickableBundle::default(),
+                bevy_transform_gizmo::GizmoTransformable,
+            ));
+        }
+
+        // `--terrain` lays the moving texture out as ground the camera can pan over forever
+        if std::env::args().any(|arg| arg == "--terrain") {
+            commands.insert_resource(Terrain {
+                material: jam_materials.add(MovingTextureMaterial {
+                    color_texture: app_assets.map_image.clone(),
+                    pattern: app_assets.pattern_image.clone(),
+                    rock: app_assets.rock_image.clone(),
+                    language: ShaderLanguage::Wgsl,
+                }),
+                chunk_size: 4.0,
+                view_distance: 3,
+                subdivisions: 4,
+                height: -1.5,
+            });
+        }
+
+        // a river bent around a swirl and a belt that only scrolls
+        let flow_map = images.add(swirl_flow_map(32));
+        commands.spawn((
+            MaterialMeshBundle {
+                mesh: Handle::default(),
+                material: flow_materials.add(FlowMaterial {
+                    color: Color::rgb(0.5, 0.8, 1.0),
+                    scroll: Vec2::new(0.0, 0.05),
+                    layer_scroll: Vec2::new(0.03, -0.04),
+                    tiling: 2.0,
+                    flow_strength: 0.2,
+                    flow_speed: 0.5,
+                    color_texture: app_assets.pattern_image.clone(),
+                    flow_map: Some(flow_map),
+                }),
+                transform: Transform::from_xyz(6.0, 0.0, 0.0),
+                ..default()
+            },
+            Name::from("flow river"),
+            SubdividedPlane {
+                subdivisions: 2,
+                size: 2.0,
+                ..default()
+            },
+            PickableBundle::default(),
+            bevy_transform_gizmo::GizmoTransformable,
+        ));
+        commands.spawn((
+            MaterialMeshBundle {
+                mesh: Handle::default(),
+                material: flow_materials.add(FlowMaterial {
+                    color: Color::WHITE,
+                    scroll: Vec2::new(0.0, 0.5),
+                    layer_scroll: Vec2::ZERO,
+                    tiling: 1.0,
+                    flow_strength: 0.0,
+                    flow_speed: 0.0,
+                    color_texture: app_assets.map_image.clone(),
+                    flow_map: None,
+                }),
+                transform: Transform::from_xyz(6.0, 0.0, 2.5)
+                    .with_scale(Vec3::new(0.5, 1.0, 2.0)),
+                ..default()
+            },
+            Name::from("flow conveyor"),
+            SubdividedPlane::default(),
+            PickableBundle::default(),
+            bevy_transform_gizmo::GizmoTransformable,
+        ));
+
         commands.spawn((
             MaterialMeshBundle {
                 mesh: mesh_assets.add(Mesh::from(shape::Plane { size: plane_size })),
@@ -176,10 +346,7 @@ fn check_load(
 
         commands.spawn((
             MaterialMeshBundle {
-                mesh: mesh_assets.add(Mesh::from(SubdividedPlane {
-                    subdivisions: 0,
-                    size: 1.0,
-                })),
+                mesh: Handle::default(),
                 material: health_materials.add(HealthBarMaterial {
                     health: 0.0,
                     color_texture: app_assets.health_image.clone(),
@@ -188,6 +355,11 @@ fn check_load(
                 ..default()
             },
             Name::from("health"),
+            SubdividedPlane {
+                subdivisions: 0,
+                size: 1.0,
+                ..default()
+            },
             PickableBundle::default(),
             bevy_transform_gizmo::GizmoTransformable,
             //Wireframe,
@@ -204,6 +376,20 @@ fn cycle_health(
     //health.health = 0.5;
 }
 
+fn cycle_dissolve(
+    time: Res<Time>,
+    mut dissolve: ResMut<Assets<DissolveMaterial>>,
+    query: Query<&Handle<DissolveMaterial>, With<CycleDissolve>>,
+) {
+    // burn away and come back every eight seconds or so
+    let progress = (1.0 - (time.elapsed_seconds() * 0.8).cos()) * 0.5;
+    for handle in query.iter() {
+        if let Some(material) = dissolve.get_mut(handle) {
+            material.progress = progress;
+        }
+    }
+}
+
 fn setup_segment_count(
     mut commands: Commands,
     mut mesh_assets: ResMut<Assets<Mesh>>,
@@ -279,6 +465,73 @@ fn setup_segment_count(
         Wireframe,
     ));
 
+    commands.spawn((
+        PbrBundle {
+            mesh: Handle::default(),
+            material: pbr_materials.add(Color::rgb(0.1, 0.1, 1.0).into()),
+            transform: Transform::from_xyz(-2.1, 0.0, 2.5),
+            ..default()
+        },
+        Name::from("icosphere"),
+        Icosphere {
+            radius: 1.0,
+            subdivisions: 2,
+        },
+        PickableBundle::default(),
+        bevy_transform_gizmo::GizmoTransformable,
+        Wireframe,
+    ));
+    commands.spawn((
+        PbrBundle {
+            mesh: Handle::default(),
+            material: pbr_materials.add(Color::rgb(0.1, 0.1, 1.0).into()),
+            transform: Transform::from_xyz(0.2, 0.0, 2.5),
+            ..default()
+        },
+        Name::from("cube sphere"),
+        CubeSphere {
+            radius: 1.0,
+            subdivisions: 5,
+        },
+        PickableBundle::default(),
+        bevy_transform_gizmo::GizmoTransformable,
+        Wireframe,
+    ));
+
+    // the same shapes faceted, to compare with the smooth ones
+    commands.spawn((
+        PbrBundle {
+            mesh: Handle::default(),
+            material: pbr_materials.add(Color::rgb(0.1, 0.1, 1.0).into()),
+            transform: Transform::from_xyz(-2.1, 0.0, 5.0),
+            ..default()
+        },
+        Name::from("faceted icosphere"),
+        Icosphere {
+            radius: 1.0,
+            subdivisions: 2,
+        },
+        Shading::new(ShadingStyle::Flat),
+        PickableBundle::default(),
+        bevy_transform_gizmo::GizmoTransformable,
+    ));
+    commands.spawn((
+        PbrBundle {
+            mesh: mesh_assets.add(Mesh::from(shape::Torus {
+                radius: 0.7,
+                ring_radius: 0.5,
+                subdivisions_segments: 8,
+                subdivisions_sides: 6,
+            })),
+            material: pbr_materials.add(Color::rgb(0.1, 0.1, 1.0).into()),
+            transform: Transform::from_xyz(2.7, 0.0, 5.0),
+            ..default()
+        },
+        Name::from("faceted torus"),
+        Shading::new(ShadingStyle::Flat),
+        PickableBundle::default(),
+        bevy_transform_gizmo::GizmoTransformable,
+    ));
 }
 
 fn setup(
@@ -289,6 +542,13 @@ fn setup(
     mut wireframe_config: ResMut<WireframeConfig>,
     mut geo_materials: ResMut<Assets<GeometryMaterial>>,
     mut world_materials: ResMut<Assets<WorldSpaceMaterial>>,
+    mut color_materials: ResMut<Assets<VertexColorMaterial>>,
+    mut rim_materials: ResMut<Assets<RimMaterial>>,
+    mut toon_materials: ResMut<Assets<ToonMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+    mut dissolve_materials: ResMut<Assets<DissolveMaterial>>,
+    mut hologram_materials: ResMut<Assets<HologramMaterial>>,
+    mut flag_materials: ResMut<Assets<FlagMaterial>>,
 ) {
     wireframe_config.global = false;
 
@@ -305,15 +565,19 @@ fn setup(
 
     commands.spawn((
         MaterialMeshBundle {
-            mesh: mesh_assets.add(Mesh::from(SubdividedPlane {
-                subdivisions: 25,
-                size: 1.0,
-            })),
-            material: geo_materials.add(GeometryMaterial {}),
+            mesh: Handle::default(),
+            material: geo_materials.add(GeometryMaterial::default()),
             transform: Transform::from_xyz(-2.0, 0.0, 0.0).with_scale(Vec3::splat(2.0)),
             ..default()
         },
         Name::from("wavy plane"),
+        SubdividedPlane {
+            subdivisions: 64,
+            size: 1.0,
+            ..default()
+        },
+        // the waves need the vertices up close, far away they're wasted
+        MeshLod::from_generator([(4.0, 1.0), (8.0, 0.25), (f32::INFINITY, 1.0 / 16.0)]),
         PickableBundle::default(),
         bevy_transform_gizmo::GizmoTransformable,
         //Wireframe,
@@ -321,15 +585,17 @@ fn setup(
 
     commands.spawn((
         PbrBundle {
-            mesh: mesh_assets.add(Mesh::from(SubdividedPlane {
-                subdivisions: 1,
-                size: 1.0,
-            })),
+            mesh: Handle::default(),
             material: pbr_materials.add(Color::rgb(0.4, 0.4, 1.0).into()),
             transform: Transform::from_xyz(0.0, 0.0, -2.0),
             ..default()
         },
         Name::from("simple divide"),
+        SubdividedPlane {
+            subdivisions: 1,
+            size: 1.0,
+            ..default()
+        },
         PickableBundle::default(),
         bevy_transform_gizmo::GizmoTransformable,
         Wireframe,
@@ -350,21 +616,316 @@ fn setup(
 
     commands.spawn((
         MaterialMeshBundle {
-            mesh: mesh_assets.add(Mesh::from(Cylinder {
+            mesh: Handle::default(),
+            material: materials.add(CoolMaterial::default()),
+            transform: Transform::from_xyz(-4.0, 0.0, 0.0),
+            ..default()
+        },
+        Name::from("cylinder"),
+        Cylinder {
+            radius: 0.75,
+            height: 2.0,
+            resolution: 32,
+            segments: 4,
+            ..default()
+        },
+        PickableBundle::default(),
+        bevy_transform_gizmo::GizmoTransformable,
+    ));
+
+    // the wave and stripe shaders in GLSL, on the same meshes
+    if std::env::args().any(|arg| arg == "--glsl") {
+        commands.spawn((
+            MaterialMeshBundle {
+                mesh: Handle::default(),
+                material: geo_materials.add(GeometryMaterial {
+                    language: ShaderLanguage::Glsl,
+                }),
+                transform: Transform::from_xyz(-2.0, 2.5, 0.0).with_scale(Vec3::splat(2.0)),
+                ..default()
+            },
+            Name::from("wavy plane glsl"),
+            SubdividedPlane {
+                subdivisions: 64,
+                size: 1.0,
+                ..default()
+            },
+            PickableBundle::default(),
+            bevy_transform_gizmo::GizmoTransformable,
+        ));
+        commands.spawn((
+            MaterialMeshBundle {
+                mesh: Handle::default(),
+                material: materials.add(CoolMaterial {
+                    language: ShaderLanguage::Glsl,
+                }),
+                transform: Transform::from_xyz(-4.0, 2.5, 0.0),
+                ..default()
+            },
+            Name::from("cylinder glsl"),
+            Cylinder {
                 radius: 0.75,
                 height: 2.0,
                 resolution: 32,
                 segments: 4,
+                ..default()
+            },
+            PickableBundle::default(),
+            bevy_transform_gizmo::GizmoTransformable,
+        ));
+    }
+
+    // spawned without a mesh, the GeneratorPlugin builds it from the generator component
+    let mut primitive = |i: usize, name: &str| {
+        (
+            MaterialMeshBundle {
+                mesh: Handle::default(),
+                material: materials.add(CoolMaterial::default()),
+                transform: Transform::from_xyz(-3.0 + i as f32 * 1.25, 0.0, -4.0),
+                ..default()
+            },
+            Name::from(name),
+            PickableBundle::default(),
+            bevy_transform_gizmo::GizmoTransformable,
+        )
+    };
+    commands.spawn(primitive(0, "frustum")).insert(Cone {
+        bottom_radius: 0.5,
+        top_radius: 0.25,
+        height: 1.0,
+        resolution: 32,
+        segments: 4,
+    });
+    commands.spawn(primitive(1, "cone")).insert(Cone::default());
+    commands.spawn(primitive(2, "capsule")).insert(Capsule::default());
+    commands.spawn(primitive(3, "tube")).insert(Tube::default());
+    commands.spawn(primitive(4, "disc")).insert(Disc::default());
+    commands.spawn(primitive(5, "annulus")).insert(Disc {
+        inner_radius: 0.25,
+        ..default()
+    });
+
+    // painted vertex colors, for checking the shading
+    let painted = color_materials.add(VertexColorMaterial {
+        color: Color::WHITE,
+    });
+    commands.spawn((
+        MaterialMeshBundle {
+            mesh: Handle::default(),
+            material: painted.clone(),
+            transform: Transform::from_xyz(-2.0, 0.0, 4.5),
+            ..default()
+        },
+        Name::from("gradient plane"),
+        SubdividedPlane {
+            subdivisions: 4,
+            size: 1.5,
+            colors: VertexColors::Gradient {
+                start: Color::RED,
+                end: Color::BLUE,
+            },
+        },
+        PickableBundle::default(),
+        bevy_transform_gizmo::GizmoTransformable,
+    ));
+    commands.spawn((
+        MaterialMeshBundle {
+            mesh: Handle::default(),
+            material: painted.clone(),
+            transform: Transform::from_xyz(0.0, 0.0, 4.5),
+            ..default()
+        },
+        Name::from("height cylinder"),
+        Cylinder {
+            colors: VertexColors::Height {
+                low: Color::DARK_GREEN,
+                high: Color::YELLOW,
+            },
+            ..default()
+        },
+        PickableBundle::default(),
+        bevy_transform_gizmo::GizmoTransformable,
+    ));
+    commands.spawn((
+        MaterialMeshBundle {
+            mesh: Handle::default(),
+            material: painted,
+            transform: Transform::from_xyz(2.0, 0.0, 4.5),
+            ..default()
+        },
+        Name::from("random faces plane"),
+        SubdividedPlane {
+            subdivisions: 4,
+            size: 1.5,
+            colors: VertexColors::RandomPerFace { seed: 0 },
+        },
+        PickableBundle::default(),
+        bevy_transform_gizmo::GizmoTransformable,
+    ));
+    commands.spawn((
+        MaterialMeshBundle {
+            mesh: Handle::default(),
+            material: rim_materials.add(RimMaterial::default()),
+            transform: Transform::from_xyz(4.0, 0.0, 4.5),
+            ..default()
+        },
+        Name::from("rim sphere"),
+        Icosphere::default(),
+        PickableBundle::default(),
+        bevy_transform_gizmo::GizmoTransformable,
+    ));
+    commands.spawn((
+        MaterialMeshBundle {
+            mesh: Handle::default(),
+            material: toon_materials.add(ToonMaterial::default()),
+            transform: Transform::from_xyz(4.2, 0.0, 2.5),
+            ..default()
+        },
+        Name::from("toon sphere"),
+        Icosphere {
+            radius: 0.6,
+            subdivisions: 3,
+        },
+        PickableBundle::default(),
+        bevy_transform_gizmo::GizmoTransformable,
+    ));
+
+    // a ramp from a cold shadow to a warm light, nearest sampling keeps the steps sharp
+    let mut ramp = Image::new(
+        Extent3d {
+            width: 4,
+            height: 1,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        [
+            [40, 40, 90, 255],
+            [90, 110, 170, 255],
+            [230, 210, 170, 255],
+            [255, 250, 235, 255],
+        ]
+        .concat(),
+        TextureFormat::Rgba8UnormSrgb,
+    );
+    ramp.sampler_descriptor = ImageSampler::nearest();
+    commands.spawn((
+        MaterialMeshBundle {
+            mesh: mesh_assets.add(Mesh::from(shape::Torus {
+                radius: 0.5,
+                ring_radius: 0.2,
+                ..default()
             })),
-            material: materials.add(CoolMaterial {}),
-            transform: Transform::from_xyz(-4.0, 0.0, 0.0),
+            material: toon_materials.add(ToonMaterial {
+                color: Color::WHITE,
+                ramp: Some(images.add(ramp)),
+                ..default()
+            }),
+            transform: Transform::from_xyz(-4.2, 0.0, 2.5),
             ..default()
         },
-        Name::from("cylinder"),
+        Name::from("toon ramp torus"),
+        PickableBundle::default(),
+        bevy_transform_gizmo::GizmoTransformable,
+    ));
+    commands.spawn((
+        MaterialMeshBundle {
+            mesh: Handle::default(),
+            material: dissolve_materials.add(DissolveMaterial::default()),
+            transform: Transform::from_xyz(-4.2, 0.0, 5.0),
+            ..default()
+        },
+        Name::from("dissolve sphere"),
+        Icosphere {
+            radius: 0.6,
+            subdivisions: 3,
+        },
+        CycleDissolve,
+        PickableBundle::default(),
+        bevy_transform_gizmo::GizmoTransformable,
+    ));
+    // the far side shows through, and the rim sphere behind it stays visible
+    commands.spawn((
+        MaterialMeshBundle {
+            mesh: Handle::default(),
+            material: hologram_materials.add(HologramMaterial {
+                two_sided: true,
+                ..default()
+            }),
+            transform: Transform::from_xyz(4.0, 0.0, 6.0),
+            ..default()
+        },
+        Name::from("hologram sphere"),
+        Icosphere {
+            radius: 0.6,
+            subdivisions: 3,
+        },
         PickableBundle::default(),
         bevy_transform_gizmo::GizmoTransformable,
     ));
 
+    // the plane stood up facing the camera, with its pinned edge on the pole
+    commands
+        .spawn((
+            PbrBundle {
+                mesh: Handle::default(),
+                material: pbr_materials.add(Color::rgb(0.6, 0.6, 0.6).into()),
+                transform: Transform::from_xyz(-6.5, 0.0, 0.0),
+                ..default()
+            },
+            Name::from("flag pole"),
+            Cylinder {
+                radius: 0.03,
+                height: 3.0,
+                ..default()
+            },
+            PickableBundle::default(),
+            bevy_transform_gizmo::GizmoTransformable,
+        ))
+        .with_children(|pole| {
+            pole.spawn((
+                MaterialMeshBundle {
+                    mesh: Handle::default(),
+                    material: flag_materials.add(FlagMaterial::default()),
+                    transform: Transform::from_xyz(0.5, 1.0, 0.0)
+                        .with_rotation(Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)),
+                    ..default()
+                },
+                Name::from("flag"),
+                SubdividedPlane {
+                    subdivisions: 32,
+                    size: 1.0,
+                    ..default()
+                },
+            ));
+        });
+
+    // the Möbius strip and Klein bottle have no outside, so draw both faces
+    let two_sided = pbr_materials.add(StandardMaterial {
+        base_color: Color::rgb(0.8, 0.5, 0.1),
+        double_sided: true,
+        cull_mode: None,
+        ..default()
+    });
+    let surfaces = [
+        ("parametric torus", ParametricSurface::torus(0.4, 0.15, 32)),
+        ("mobius strip", ParametricSurface::mobius(0.4, 0.3, 64)),
+        ("klein bottle", ParametricSurface::klein_bottle(1.0, 48)),
+        ("superquadric", ParametricSurface::superquadric(0.5, 0.3, 0.3, 32)),
+    ];
+    for (i, (name, surface)) in surfaces.into_iter().enumerate() {
+        commands.spawn((
+            PbrBundle {
+                mesh: mesh_assets.add(Mesh::from(surface)),
+                material: two_sided.clone(),
+                transform: Transform::from_xyz(-2.0 + i as f32 * 1.5, 0.0, -6.0),
+                ..default()
+            },
+            Name::from(name),
+            PickableBundle::default(),
+            bevy_transform_gizmo::GizmoTransformable,
+        ));
+    }
+
     commands.spawn((
         PbrBundle {
             mesh: mesh_assets.add(Mesh::from(shape::UVSphere {
@@ -396,7 +957,7 @@ fn setup(
     commands.spawn((
         MaterialMeshBundle {
             mesh: mesh_assets.add(Mesh::from(shape::UVSphere::default())),
-            material: materials.add(CoolMaterial {}),
+            material: materials.add(CoolMaterial::default()),
             transform: Transform::from_xyz(2.0, 0.0, 0.0),
             ..default()
         },
@@ -406,6 +967,37 @@ fn setup(
     ));
 }
 
+/// A flow map turning around the middle, repeating and in linear color so the directions stay exact.
+fn swirl_flow_map(size: u32) -> Image {
+    let mut data = Vec::with_capacity((size * size * 4) as usize);
+    for y in 0..size {
+        for x in 0..size {
+            let offset = Vec2::new(x as f32, y as f32) / (size - 1) as f32 - Vec2::splat(0.5);
+            // around the middle, and a little inwards
+            let direction = (offset.perp() - offset * 0.3).normalize_or_zero();
+            let color = direction * 0.5 + Vec2::splat(0.5);
+            data.extend([(color.x * 255.0) as u8, (color.y * 255.0) as u8, 0, 255]);
+        }
+    }
+    let mut image = Image::new(
+        Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8Unorm,
+    );
+    let mut descriptor = SamplerDescriptor::default();
+    descriptor.address_mode_u = AddressMode::Repeat;
+    descriptor.address_mode_v = AddressMode::Repeat;
+    descriptor.mag_filter = FilterMode::Linear;
+    descriptor.min_filter = FilterMode::Linear;
+    image.sampler_descriptor = ImageSampler::Descriptor(descriptor);
+    image
+}
+
 fn spawn_camera(mut commands: Commands) {
     let focus: Vec3 = Vec3::ZERO;
     let mut transform = Transform::default();
@@ -433,15 +1025,70 @@ fn spawn_camera(mut commands: Commands) {
         ..Default::default()
     };
 
-    commands.spawn((
+    // `--split` renders the scene twice side by side with linked orbiting,
+    // to compare material variants from the same angle
+    let split = std::env::args().any(|arg| arg == "--split");
+
+    let mut main_camera = commands.spawn((
         camera,
         PanOrbitCamera {
             radius: (transform.translation - focus).length(),
             focus,
+            linked: split,
             ..Default::default()
         },
         PickingCameraBundle::default(),
         bevy_transform_gizmo::GizmoPickSource::default(),
         Name::from("Main Camera"),
     ));
+
+    // `--turntable` and `--flythrough` move the camera for recording material demos
+    if std::env::args().any(|arg| arg == "--turntable") {
+        main_camera.insert(CameraPath::turntable(20.0));
+    } else if std::env::args().any(|arg| arg == "--flythrough") {
+        main_camera.insert(CameraPath {
+            looping: PathLooping::Loop,
+            easing: PathEasing::Linear,
+            ..CameraPath::spline(
+                vec![
+                    CameraKeyframe::new(Vec3::ZERO, 12.0, 0.0, -0.3),
+                    CameraKeyframe::new(Vec3::new(-4.0, 0.0, 0.0), 4.0, -0.8, -0.4),
+                    CameraKeyframe::new(Vec3::new(-2.0, 0.0, 0.0), 3.0, 0.0, -1.0),
+                    CameraKeyframe::new(Vec3::new(2.0, 0.0, 0.0), 4.0, 0.8, -0.2),
+                ],
+                4.0,
+            )
+        });
+    }
+
+    if split {
+        main_camera.insert(OrbitViewport::left_half());
+
+        let right_focus = Vec3::new(2.0, 0.0, 0.0);
+        let right_transform = Transform::from_translation(right_focus + Vec3::new(0.0, 0.0, 12.0));
+        commands.spawn((
+            Camera3dBundle {
+                camera: Camera {
+                    priority: 1,
+                    ..default()
+                },
+                // the main camera already cleared the whole window
+                camera_3d: Camera3d {
+                    clear_color: ClearColorConfig::None,
+                    ..default()
+                },
+                transform: right_transform,
+                ..default()
+            },
+            PanOrbitCamera {
+                radius: (right_transform.translation - right_focus).length(),
+                focus: right_focus,
+                linked: true,
+                ..Default::default()
+            },
+            OrbitViewport::right_half(),
+            PickingCameraBundle::default(),
+            Name::from("Compare Camera"),
+        ));
+    }
 }
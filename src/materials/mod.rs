@@ -1,14 +1,138 @@
+use std::hash::Hash;
+
 use bevy::{
+    asset::HandleId,
     prelude::*,
     reflect::TypeUuid,
-    render::{render_resource::{AsBindGroup, ShaderRef, RenderPipelineDescriptor, SpecializedMeshPipelineError}, mesh::MeshVertexBufferLayout},
+    render::{render_resource::{AsBindGroup, BlendComponent, BlendFactor, BlendOperation, BlendState, Face, ShaderRef, ShaderType, RenderPipelineDescriptor, SpecializedMeshPipelineError}, mesh::MeshVertexBufferLayout},
 pbr::{MaterialPipelineKey, MaterialPipeline},
 };
 
-#[derive(AsBindGroup, TypeUuid, Clone, Reflect)]
+/// Which files a material's shaders come from. The WGSL files are the `Material` defaults,
+/// `specialize` swaps in the GLSL versions next to them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect, FromReflect)]
+pub enum ShaderLanguage {
+    #[default]
+    Wgsl,
+    Glsl,
+}
+
+impl ShaderLanguage {
+    /// `glsl` is the vertex and fragment shader paths.
+    fn specialize(self, descriptor: &mut RenderPipelineDescriptor, glsl: [&'static str; 2]) {
+        let [vertex, fragment] = glsl;
+        if self == ShaderLanguage::Glsl {
+            // bevy only takes `main` as the entry point of a GLSL shader
+            descriptor.vertex.shader = Handle::weak(HandleId::from(vertex));
+            descriptor.vertex.entry_point = "main".into();
+            let fragment_state = descriptor.fragment.as_mut().unwrap();
+            fragment_state.shader = Handle::weak(HandleId::from(fragment));
+            fragment_state.entry_point = "main".into();
+        }
+    }
+}
+
+/// What a material binding holds, to compare with the shader's `@group(1)` variables.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BindingKind {
+    Uniform,
+    Storage,
+    Texture,
+    Sampler,
+}
+
+/// The `@group(1)` bindings the `AsBindGroup` derive makes for a material, written out
+/// for `--check-shaders` since the derive only gives up its layout to a render device.
+/// Keep them next to the `#[uniform]`, `#[texture]` and `#[sampler]` attributes they mirror,
+/// the shader check tests compare them with the derived layout on machines with an adapter.
+pub trait MaterialBindings {
+    const BINDINGS: &'static [(u32, BindingKind)];
+}
+
+/// Something done once for every material, like adding its `MaterialPlugin`.
+pub trait MaterialVisitor {
+    /// `glsl` is the GLSL versions of the material's shaders, empty if it only has WGSL.
+    fn visit<M: Material + MaterialBindings>(&mut self, glsl: &[&'static str])
+    where
+        M::Data: PartialEq + Eq + Hash + Clone;
+}
+
+/// The one list of materials, [`MaterialsPlugin`] and `--check-shaders` both go through it.
+pub fn visit_materials(visitor: &mut impl MaterialVisitor) {
+    visitor.visit::<CoolMaterial>(&COOL_GLSL);
+    visitor.visit::<GeometryMaterial>(&GEOMETRY_GLSL);
+    visitor.visit::<GLSLMaterial>(&[]);
+    visitor.visit::<MovingTextureMaterial>(&MOVING_TEXTURE_GLSL);
+    visitor.visit::<WorldSpaceMaterial>(&[]);
+    visitor.visit::<HealthBarMaterial>(&[]);
+    visitor.visit::<VertexColorMaterial>(&[]);
+    visitor.visit::<RimMaterial>(&[]);
+    visitor.visit::<ToonMaterial>(&[]);
+    visitor.visit::<ToonOutlineMaterial>(&[]);
+    visitor.visit::<DissolveMaterial>(&[]);
+    visitor.visit::<FlowMaterial>(&[]);
+    visitor.visit::<HologramMaterial>(&[]);
+    visitor.visit::<FlagMaterial>(&[]);
+}
+
+/// Adds the `MaterialPlugin` of every material.
+pub struct MaterialsPlugin;
+
+impl Plugin for MaterialsPlugin {
+    fn build(&self, app: &mut App) {
+        struct AddMaterialPlugins<'a>(&'a mut App);
+
+        impl MaterialVisitor for AddMaterialPlugins<'_> {
+            fn visit<M: Material + MaterialBindings>(&mut self, _glsl: &[&'static str])
+            where
+                M::Data: PartialEq + Eq + Hash + Clone,
+            {
+                self.0.add_plugin(MaterialPlugin::<M>::default());
+            }
+        }
+
+        visit_materials(&mut AddMaterialPlugins(app));
+    }
+}
+
+/// The GLSL versions of the vertex and fragment shaders of the materials with a [`ShaderLanguage`].
+pub const COOL_GLSL: [&str; 2] = ["shaders/my.vert", "shaders/my.frag"];
+pub const GEOMETRY_GLSL: [&str; 2] = ["shaders/geo.vert", "shaders/geo.frag"];
+pub const MOVING_TEXTURE_GLSL: [&str; 2] = ["shaders/moving.vert", "shaders/moving.frag"];
+
+/// Loads the GLSL versions of the shaders, the pipelines only get weak handles to them.
+pub struct GlslShaderPlugin;
+
+impl Plugin for GlslShaderPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_startup_system(load_glsl_shaders);
+    }
+}
+
+#[derive(Resource)]
+struct GlslShaders(Vec<Handle<Shader>>);
+
+fn load_glsl_shaders(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let shaders = [COOL_GLSL, GEOMETRY_GLSL, MOVING_TEXTURE_GLSL]
+        .concat()
+        .into_iter()
+        .map(|path| asset_server.load(path))
+        .collect();
+    commands.insert_resource(GlslShaders(shaders));
+}
+
+#[derive(AsBindGroup, TypeUuid, Clone, Reflect, Default)]
 #[uuid = "f690fdae-d598-45ab-8225-97e2a3f056e0"]
+#[bind_group_data(ShaderLanguage)]
 pub struct CoolMaterial {
 //    #[uniform(0)]
+    pub language: ShaderLanguage,
+}
+
+impl From<&CoolMaterial> for ShaderLanguage {
+    fn from(material: &CoolMaterial) -> Self {
+        material.language
+    }
 }
 
 impl Material for CoolMaterial {
@@ -26,19 +150,32 @@ impl Material for CoolMaterial {
         _pipeline: &MaterialPipeline<Self>,
         descriptor: &mut RenderPipelineDescriptor,
         _layout: &MeshVertexBufferLayout, // an entitys layout
-        _key: MaterialPipelineKey<Self>, // an entitys key
+        key: MaterialPipelineKey<Self>, // an entitys key
     ) -> Result<(), SpecializedMeshPipelineError> {
 
         // this doesn't cull any faces
         descriptor.primitive.cull_mode = None;
+        key.bind_group_data.specialize(descriptor, COOL_GLSL);
         Ok(())
     }
 }
 
-#[derive(AsBindGroup, TypeUuid, Clone, Reflect)]
+impl MaterialBindings for CoolMaterial {
+    const BINDINGS: &'static [(u32, BindingKind)] = &[];
+}
+
+#[derive(AsBindGroup, TypeUuid, Clone, Reflect, Default)]
 #[uuid = "215519A9-0958-4EDF-A3FF-084C82232E06"]
+#[bind_group_data(ShaderLanguage)]
 pub struct GeometryMaterial {
 //    #[uniform(0)]
+    pub language: ShaderLanguage,
+}
+
+impl From<&GeometryMaterial> for ShaderLanguage {
+    fn from(material: &GeometryMaterial) -> Self {
+        material.language
+    }
 }
 
 impl Material for GeometryMaterial {
@@ -48,10 +185,24 @@ impl Material for GeometryMaterial {
     fn fragment_shader() -> ShaderRef {
         "shaders/geo_frag.wgsl".into()
     }
+    fn specialize(
+        _pipeline: &MaterialPipeline<Self>,
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &MeshVertexBufferLayout, // an entitys layout
+        key: MaterialPipelineKey<Self>, // an entitys key
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        key.bind_group_data.specialize(descriptor, GEOMETRY_GLSL);
+        Ok(())
+    }
+}
+
+impl MaterialBindings for GeometryMaterial {
+    const BINDINGS: &'static [(u32, BindingKind)] = &[];
 }
 
 #[derive(AsBindGroup, TypeUuid, Clone, Reflect)]
 #[uuid = "D8E4526D-3C81-47B6-8D23-E88D578057A0"]
+#[bind_group_data(ShaderLanguage)]
 pub struct MovingTextureMaterial {
 //    #[uniform(0)]
     #[texture(1)]
@@ -61,6 +212,13 @@ pub struct MovingTextureMaterial {
     pub pattern: Handle<Image>,
     #[texture(4)]
     pub rock: Handle<Image>,
+    pub language: ShaderLanguage,
+}
+
+impl From<&MovingTextureMaterial> for ShaderLanguage {
+    fn from(material: &MovingTextureMaterial) -> Self {
+        material.language
+    }
 }
 
 impl Material for MovingTextureMaterial {
@@ -70,12 +228,32 @@ impl Material for MovingTextureMaterial {
     fn fragment_shader() -> ShaderRef {
         "shaders/moving_frag.wgsl".into()
     }
+    fn specialize(
+        _pipeline: &MaterialPipeline<Self>,
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &MeshVertexBufferLayout, // an entitys layout
+        key: MaterialPipelineKey<Self>, // an entitys key
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        key.bind_group_data.specialize(descriptor, MOVING_TEXTURE_GLSL);
+        Ok(())
+    }
+}
+
+impl MaterialBindings for MovingTextureMaterial {
+    const BINDINGS: &'static [(u32, BindingKind)] = &[
+        (1, BindingKind::Texture),
+        (2, BindingKind::Sampler),
+        (3, BindingKind::Texture),
+        (4, BindingKind::Texture),
+    ];
 }
 
 #[derive(AsBindGroup, TypeUuid, Clone, Reflect)]
 #[uuid = "5F9B8800-B148-487B-B43F-50CC36CB8114"]
+#[bind_group_data(GLSLMaterialKey)]
+#[uniform(0, GLSLMaterialUniform)]
 pub struct GLSLMaterial {
-    #[uniform(0)]
+    /// Multiplied with the texture.
     pub color: Color,
     #[texture(1)]
     #[sampler(2)]
@@ -83,6 +261,40 @@ pub struct GLSLMaterial {
     pub alpha_mode: AlphaMode,
 }
 
+/// The uniform block in `tex.frag`, the cutoff only matters with [`AlphaMode::Mask`].
+#[derive(Clone, Default, ShaderType)]
+pub struct GLSLMaterialUniform {
+    pub color: Vec4,
+    pub alpha_cutoff: f32,
+}
+
+impl From<&GLSLMaterial> for GLSLMaterialUniform {
+    fn from(material: &GLSLMaterial) -> Self {
+        let alpha_cutoff = match material.alpha_mode {
+            AlphaMode::Mask(cutoff) => cutoff,
+            _ => 0.5,
+        };
+        Self {
+            color: material.color.as_linear_rgba_f32().into(),
+            alpha_cutoff,
+        }
+    }
+}
+
+/// Everything from the material that changes the pipeline.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct GLSLMaterialKey {
+    alpha_mask: bool,
+}
+
+impl From<&GLSLMaterial> for GLSLMaterialKey {
+    fn from(material: &GLSLMaterial) -> Self {
+        Self {
+            alpha_mask: matches!(material.alpha_mode, AlphaMode::Mask(_)),
+        }
+    }
+}
+
 impl Material for GLSLMaterial {
     fn vertex_shader() -> ShaderRef {
         "shaders/tex.vert".into()
@@ -90,18 +302,33 @@ impl Material for GLSLMaterial {
     fn fragment_shader() -> ShaderRef {
         "shaders/tex.frag".into()
     }
+    fn alpha_mode(&self) -> AlphaMode {
+        self.alpha_mode
+    }
     fn specialize(
         _pipeline: &MaterialPipeline<Self>,
         descriptor: &mut RenderPipelineDescriptor,
         _layout: &MeshVertexBufferLayout, // an entitys layout
-        _key: MaterialPipelineKey<Self>, // an entitys key
+        key: MaterialPipelineKey<Self>, // an entitys key
     ) -> Result<(), SpecializedMeshPipelineError> {
         descriptor.vertex.entry_point = "main".into();
-        descriptor.fragment.as_mut().unwrap().entry_point = "main".into();
+        let fragment = descriptor.fragment.as_mut().unwrap();
+        fragment.entry_point = "main".into();
+        if key.bind_group_data.alpha_mask {
+            fragment.shader_defs.push("ALPHA_MASK".into());
+        }
         Ok(())
     }
 }
 
+impl MaterialBindings for GLSLMaterial {
+    const BINDINGS: &'static [(u32, BindingKind)] = &[
+        (0, BindingKind::Uniform),
+        (1, BindingKind::Texture),
+        (2, BindingKind::Sampler),
+    ];
+}
+
 #[derive(AsBindGroup, TypeUuid, Clone, Reflect)]
 #[uuid = "545B018A-F802-4BD0-9E31-2F94361BE939"]
 pub struct HealthBarMaterial {
@@ -120,6 +347,14 @@ impl Material for HealthBarMaterial {
     }
 }
 
+impl MaterialBindings for HealthBarMaterial {
+    const BINDINGS: &'static [(u32, BindingKind)] = &[
+        (0, BindingKind::Uniform),
+        (1, BindingKind::Texture),
+        (2, BindingKind::Sampler),
+    ];
+}
+
 #[derive(AsBindGroup, TypeUuid, Clone, Reflect)]
 #[uuid = "3A7DAA59-99DB-44D3-A778-2F1A6B1DFA5E"]
 pub struct WorldSpaceMaterial {
@@ -133,3 +368,561 @@ impl Material for WorldSpaceMaterial {
         "shaders/world_frag.wgsl".into()
     }
 }
+
+impl MaterialBindings for WorldSpaceMaterial {
+    const BINDINGS: &'static [(u32, BindingKind)] = &[];
+}
+
+/// Draws `Mesh::ATTRIBUTE_COLOR` with a little lighting, for checking painted generator colors.
+/// Meshes without colors come out white.
+#[derive(AsBindGroup, TypeUuid, Clone, Reflect)]
+#[uuid = "9C1B6E2E-4A0F-4C64-9D6B-1F3F8C2A7E51"]
+pub struct VertexColorMaterial {
+    /// Colors are multiplied with this.
+    #[uniform(0)]
+    pub color: Color,
+}
+
+impl Material for VertexColorMaterial {
+    fn vertex_shader() -> ShaderRef {
+        "shaders/vertex_color.wgsl".into()
+    }
+    fn fragment_shader() -> ShaderRef {
+        "shaders/vertex_color.wgsl".into()
+    }
+    fn specialize(
+        _pipeline: &MaterialPipeline<Self>,
+        descriptor: &mut RenderPipelineDescriptor,
+        layout: &MeshVertexBufferLayout, // an entitys layout
+        _key: MaterialPipelineKey<Self>, // an entitys key
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        // the default layout leaves out the color, so ask for it when the mesh has one
+        let mut attributes = vec![
+            Mesh::ATTRIBUTE_POSITION.at_shader_location(0),
+            Mesh::ATTRIBUTE_NORMAL.at_shader_location(1),
+        ];
+        if layout.contains(Mesh::ATTRIBUTE_COLOR) {
+            attributes.push(Mesh::ATTRIBUTE_COLOR.at_shader_location(2));
+            descriptor.vertex.shader_defs.push("VERTEX_COLORS".into());
+            descriptor
+                .fragment
+                .as_mut()
+                .unwrap()
+                .shader_defs
+                .push("VERTEX_COLORS".into());
+        }
+        descriptor.vertex.buffers = vec![layout.get_layout(&attributes)?];
+        Ok(())
+    }
+}
+
+impl MaterialBindings for VertexColorMaterial {
+    const BINDINGS: &'static [(u32, BindingKind)] = &[(0, BindingKind::Uniform)];
+}
+
+/// Brightens the edges facing away from the camera, the fresnel look used for
+/// selection highlights and holograms.
+#[derive(AsBindGroup, TypeUuid, Clone, Reflect)]
+#[uuid = "6E3A8D41-2B7C-4F0E-9A55-C8D1F2B3A4E6"]
+pub struct RimMaterial {
+    #[uniform(0)]
+    pub color: Color,
+    #[uniform(0)]
+    pub rim_color: Color,
+    /// Higher makes the rim thinner.
+    #[uniform(0)]
+    pub power: f32,
+    #[uniform(0)]
+    pub intensity: f32,
+}
+
+impl Default for RimMaterial {
+    fn default() -> Self {
+        Self {
+            color: Color::rgb(0.1, 0.1, 0.2),
+            rim_color: Color::CYAN,
+            power: 3.0,
+            intensity: 1.5,
+        }
+    }
+}
+
+impl Material for RimMaterial {
+    fn vertex_shader() -> ShaderRef {
+        "shaders/rim.wgsl".into()
+    }
+    fn fragment_shader() -> ShaderRef {
+        "shaders/rim.wgsl".into()
+    }
+}
+
+impl MaterialBindings for RimMaterial {
+    const BINDINGS: &'static [(u32, BindingKind)] = &[(0, BindingKind::Uniform)];
+}
+
+/// Cel shading, the light from bevy's point and directional lights comes in hard steps.
+/// [`crate::toon::ToonPlugin`] draws the outline and shows these in an inspector window.
+#[derive(AsBindGroup, TypeUuid, Clone, Reflect)]
+#[uuid = "B7D2F0C3-5E14-4A8B-9C6D-2E7F1A3B5C90"]
+#[bind_group_data(ToonMaterialKey)]
+pub struct ToonMaterial {
+    #[uniform(0)]
+    pub color: Color,
+    #[uniform(0)]
+    pub specular_color: Color,
+    #[uniform(0)]
+    pub rim_color: Color,
+    /// Steps between dark and fully lit, unused with a `ramp`.
+    #[uniform(0)]
+    pub bands: u32,
+    /// Higher makes the highlight smaller.
+    #[uniform(0)]
+    pub glossiness: f32,
+    /// How bright the blinn-phong highlight has to be to show, 1 hides it.
+    #[uniform(0)]
+    pub specular_threshold: f32,
+    /// How close to the silhouette the rim starts, 1 hides it.
+    #[uniform(0)]
+    pub rim_threshold: f32,
+    /// Looked up left to right by how lit the surface is, replaces the `bands`.
+    #[texture(1)]
+    #[sampler(2)]
+    pub ramp: Option<Handle<Image>>,
+    pub outline_color: Color,
+    /// In pixels, 0 for no outline.
+    pub outline_width: f32,
+}
+
+impl Default for ToonMaterial {
+    fn default() -> Self {
+        Self {
+            color: Color::rgb(0.9, 0.4, 0.2),
+            specular_color: Color::rgb(0.9, 0.9, 0.9),
+            rim_color: Color::rgb(0.3, 0.3, 0.3),
+            bands: 3,
+            glossiness: 32.0,
+            specular_threshold: 0.5,
+            rim_threshold: 0.7,
+            ramp: None,
+            outline_color: Color::BLACK,
+            outline_width: 3.0,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct ToonMaterialKey {
+    ramp: bool,
+}
+
+impl From<&ToonMaterial> for ToonMaterialKey {
+    fn from(material: &ToonMaterial) -> Self {
+        Self {
+            ramp: material.ramp.is_some(),
+        }
+    }
+}
+
+impl Material for ToonMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/toon.wgsl".into()
+    }
+    fn specialize(
+        _pipeline: &MaterialPipeline<Self>,
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &MeshVertexBufferLayout, // an entitys layout
+        key: MaterialPipelineKey<Self>, // an entitys key
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        if key.bind_group_data.ramp {
+            descriptor
+                .fragment
+                .as_mut()
+                .unwrap()
+                .shader_defs
+                .push("RAMP".into());
+        }
+        Ok(())
+    }
+}
+
+impl MaterialBindings for ToonMaterial {
+    const BINDINGS: &'static [(u32, BindingKind)] = &[
+        (0, BindingKind::Uniform),
+        (1, BindingKind::Texture),
+        (2, BindingKind::Sampler),
+    ];
+}
+
+/// The back faces of a mesh pushed out along its normals, what shows past the
+/// front faces is the outline. The mesh needs smooth normals, the faces of a
+/// flat shaded one come apart at the corners.
+#[derive(AsBindGroup, TypeUuid, Clone, Reflect)]
+#[uuid = "0F4C9A2E-83B1-4D57-A6E0-5B2C7D9E1F38"]
+pub struct ToonOutlineMaterial {
+    #[uniform(0)]
+    pub color: Color,
+    /// In pixels.
+    #[uniform(0)]
+    pub width: f32,
+}
+
+impl Material for ToonOutlineMaterial {
+    fn vertex_shader() -> ShaderRef {
+        "shaders/toon_outline.wgsl".into()
+    }
+    fn fragment_shader() -> ShaderRef {
+        "shaders/toon_outline.wgsl".into()
+    }
+    fn specialize(
+        _pipeline: &MaterialPipeline<Self>,
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &MeshVertexBufferLayout, // an entitys layout
+        _key: MaterialPipelineKey<Self>, // an entitys key
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        // only the inside of the hull, the mesh itself covers the rest
+        descriptor.primitive.cull_mode = Some(Face::Front);
+        Ok(())
+    }
+}
+
+impl MaterialBindings for ToonOutlineMaterial {
+    const BINDINGS: &'static [(u32, BindingKind)] = &[(0, BindingKind::Uniform)];
+}
+
+/// Burns a mesh away along a noise pattern, for spawning and despawning things.
+/// `progress` runs from 0, all there, to 1, all gone.
+#[derive(AsBindGroup, TypeUuid, Clone, Reflect)]
+#[uuid = "A3E85C17-6D2B-4F90-B1C4-7E9D0F2A6B83"]
+#[bind_group_data(DissolveMaterialKey)]
+pub struct DissolveMaterial {
+    #[uniform(0)]
+    pub color: Color,
+    #[uniform(0)]
+    pub edge_color: Color,
+    #[uniform(0)]
+    pub progress: f32,
+    /// How far into the noise the glowing edge reaches.
+    #[uniform(0)]
+    pub edge_width: f32,
+    /// Noise cells per unit of the mesh, for the procedural noise.
+    #[uniform(0)]
+    pub noise_scale: f32,
+    /// Red channel read at the mesh UVs, the procedural noise from `common.wgsl` when `None`.
+    #[texture(1)]
+    #[sampler(2)]
+    pub noise: Option<Handle<Image>>,
+}
+
+impl Default for DissolveMaterial {
+    fn default() -> Self {
+        Self {
+            color: Color::rgb(0.6, 0.6, 0.65),
+            edge_color: Color::rgb(1.0, 0.5, 0.1),
+            progress: 0.0,
+            edge_width: 0.05,
+            noise_scale: 4.0,
+            noise: None,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct DissolveMaterialKey {
+    noise_texture: bool,
+}
+
+impl From<&DissolveMaterial> for DissolveMaterialKey {
+    fn from(material: &DissolveMaterial) -> Self {
+        Self {
+            noise_texture: material.noise.is_some(),
+        }
+    }
+}
+
+impl Material for DissolveMaterial {
+    fn vertex_shader() -> ShaderRef {
+        "shaders/dissolve.wgsl".into()
+    }
+    fn fragment_shader() -> ShaderRef {
+        "shaders/dissolve.wgsl".into()
+    }
+    // the burnt parts are discarded, so it sorts like an opaque material
+    fn alpha_mode(&self) -> AlphaMode {
+        AlphaMode::Mask(0.5)
+    }
+    fn specialize(
+        _pipeline: &MaterialPipeline<Self>,
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &MeshVertexBufferLayout, // an entitys layout
+        key: MaterialPipelineKey<Self>, // an entitys key
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        if key.bind_group_data.noise_texture {
+            // the vertex shader passes the UVs on for the texture
+            descriptor.vertex.shader_defs.push("NOISE_TEXTURE".into());
+            descriptor
+                .fragment
+                .as_mut()
+                .unwrap()
+                .shader_defs
+                .push("NOISE_TEXTURE".into());
+        }
+        Ok(())
+    }
+}
+
+impl MaterialBindings for DissolveMaterial {
+    const BINDINGS: &'static [(u32, BindingKind)] = &[
+        (0, BindingKind::Uniform),
+        (1, BindingKind::Texture),
+        (2, BindingKind::Sampler),
+    ];
+}
+
+/// Scrolls a texture across the mesh, for rivers and conveyor belts. A second layer
+/// scrolling another way breaks up the repetition, and a flow map bends the scrolling
+/// per texel. The textures need a repeating sampler.
+#[derive(AsBindGroup, TypeUuid, Clone, Reflect)]
+#[uuid = "C41F7B2D-9E06-4A3C-8D15-6B0E2F9A7C54"]
+#[bind_group_data(FlowMaterialKey)]
+pub struct FlowMaterial {
+    /// Multiplied with the texture.
+    #[uniform(0)]
+    pub color: Color,
+    /// UVs per second.
+    #[uniform(0)]
+    pub scroll: Vec2,
+    /// UVs per second of a second copy blended over the first, zero for a single layer.
+    #[uniform(0)]
+    pub layer_scroll: Vec2,
+    /// Times the texture repeats across the mesh.
+    #[uniform(0)]
+    pub tiling: f32,
+    /// How far the flow map moves the UVs before they jump back.
+    #[uniform(0)]
+    pub flow_strength: f32,
+    /// Jumps back per second.
+    #[uniform(0)]
+    pub flow_speed: f32,
+    #[texture(1)]
+    #[sampler(2)]
+    pub color_texture: Handle<Image>,
+    /// The direction to flow in red and green, 0.5 is still.
+    #[texture(3)]
+    #[sampler(4)]
+    pub flow_map: Option<Handle<Image>>,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct FlowMaterialKey {
+    two_layers: bool,
+    flow_map: bool,
+}
+
+impl From<&FlowMaterial> for FlowMaterialKey {
+    fn from(material: &FlowMaterial) -> Self {
+        Self {
+            two_layers: material.layer_scroll != Vec2::ZERO,
+            flow_map: material.flow_map.is_some(),
+        }
+    }
+}
+
+impl Material for FlowMaterial {
+    fn vertex_shader() -> ShaderRef {
+        "shaders/flow.wgsl".into()
+    }
+    fn fragment_shader() -> ShaderRef {
+        "shaders/flow.wgsl".into()
+    }
+    fn specialize(
+        _pipeline: &MaterialPipeline<Self>,
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &MeshVertexBufferLayout, // an entitys layout
+        key: MaterialPipelineKey<Self>, // an entitys key
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        let shader_defs = &mut descriptor.fragment.as_mut().unwrap().shader_defs;
+        if key.bind_group_data.two_layers {
+            shader_defs.push("TWO_LAYERS".into());
+        }
+        if key.bind_group_data.flow_map {
+            shader_defs.push("FLOW_MAP".into());
+        }
+        Ok(())
+    }
+}
+
+impl MaterialBindings for FlowMaterial {
+    const BINDINGS: &'static [(u32, BindingKind)] = &[
+        (0, BindingKind::Uniform),
+        (1, BindingKind::Texture),
+        (2, BindingKind::Sampler),
+        (3, BindingKind::Texture),
+        (4, BindingKind::Sampler),
+    ];
+}
+
+/// A see-through projection with climbing scanlines, a flickering brightness and a
+/// bright fresnel edge. It's blended additively, so it only ever lightens what's behind it.
+#[derive(AsBindGroup, TypeUuid, Clone, Reflect)]
+#[uuid = "E82B4D6A-1C73-4F25-9B08-3D5A7E0C1F96"]
+#[bind_group_data(HologramMaterialKey)]
+pub struct HologramMaterial {
+    /// The alpha scales the whole effect.
+    #[uniform(0)]
+    pub color: Color,
+    /// Scanlines per world unit of height.
+    #[uniform(0)]
+    pub scanline_density: f32,
+    /// World units per second the scanlines move down.
+    #[uniform(0)]
+    pub scanline_speed: f32,
+    /// 0 for a steady brightness, 1 flickers all the way to black.
+    #[uniform(0)]
+    pub flicker: f32,
+    /// Higher makes the bright edge thinner.
+    #[uniform(0)]
+    pub fresnel_power: f32,
+    /// Hide what's drawn later behind it. Off lets overlapping holograms all show,
+    /// at the cost of them not sorting among themselves.
+    pub depth_write: bool,
+    /// Draw the back faces too, so the far side shows through.
+    pub two_sided: bool,
+}
+
+impl Default for HologramMaterial {
+    fn default() -> Self {
+        Self {
+            color: Color::rgba(0.2, 0.8, 1.0, 1.0),
+            scanline_density: 20.0,
+            scanline_speed: 0.2,
+            flicker: 0.15,
+            fresnel_power: 2.0,
+            depth_write: false,
+            two_sided: false,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct HologramMaterialKey {
+    depth_write: bool,
+    two_sided: bool,
+}
+
+impl From<&HologramMaterial> for HologramMaterialKey {
+    fn from(material: &HologramMaterial) -> Self {
+        Self {
+            depth_write: material.depth_write,
+            two_sided: material.two_sided,
+        }
+    }
+}
+
+impl Material for HologramMaterial {
+    fn vertex_shader() -> ShaderRef {
+        "shaders/hologram.wgsl".into()
+    }
+    fn fragment_shader() -> ShaderRef {
+        "shaders/hologram.wgsl".into()
+    }
+    // drawn after the opaque meshes, back to front
+    fn alpha_mode(&self) -> AlphaMode {
+        AlphaMode::Blend
+    }
+    fn specialize(
+        _pipeline: &MaterialPipeline<Self>,
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &MeshVertexBufferLayout, // an entitys layout
+        key: MaterialPipelineKey<Self>, // an entitys key
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        if key.bind_group_data.two_sided {
+            descriptor.primitive.cull_mode = None;
+        }
+        if let Some(depth_stencil) = descriptor.depth_stencil.as_mut() {
+            depth_stencil.depth_write_enabled = key.bind_group_data.depth_write;
+        }
+        // the color is added on top, scaled by the alpha
+        let blend = BlendState {
+            color: BlendComponent {
+                src_factor: BlendFactor::SrcAlpha,
+                dst_factor: BlendFactor::One,
+                operation: BlendOperation::Add,
+            },
+            alpha: BlendComponent::OVER,
+        };
+        for target in descriptor.fragment.as_mut().unwrap().targets.iter_mut().flatten() {
+            target.blend = Some(blend);
+        }
+        Ok(())
+    }
+}
+
+impl MaterialBindings for HologramMaterial {
+    const BINDINGS: &'static [(u32, BindingKind)] = &[(0, BindingKind::Uniform)];
+}
+
+/// Waves a vertical [`crate::plane::SubdividedPlane`] like a flag in the wind. The edge at
+/// UV x 0 is pinned to the pole and the cloth moves more the further from it, along the
+/// plane's normal. The normals are worked out again from the waves so the folds catch the light.
+#[derive(AsBindGroup, TypeUuid, Clone, Reflect)]
+#[uuid = "5D0C8E3B-7A19-4B62-8F4E-1C6A9B2D0E75"]
+pub struct FlagMaterial {
+    #[uniform(0)]
+    pub color: Color,
+    /// In world space. The waves run along the part of it in the plane of the flag,
+    /// the part through the flag leans it towards the side it blows to.
+    #[uniform(0)]
+    pub wind_direction: Vec3,
+    /// How fast the waves run along the cloth, mesh units per second.
+    #[uniform(0)]
+    pub wind_speed: f32,
+    /// 0 for a steady wind, 1 drops it to nothing between gusts.
+    #[uniform(0)]
+    pub gust_strength: f32,
+    /// Gusts per second.
+    #[uniform(0)]
+    pub gust_frequency: f32,
+    /// In mesh units.
+    #[uniform(0)]
+    pub wave_length: f32,
+    /// How far the free edge moves, in mesh units.
+    #[uniform(0)]
+    pub amplitude: f32,
+}
+
+impl Default for FlagMaterial {
+    fn default() -> Self {
+        Self {
+            color: Color::rgb(0.8, 0.1, 0.15),
+            wind_direction: Vec3::new(0.0, 0.0, 1.0),
+            wind_speed: 0.8,
+            gust_strength: 0.5,
+            gust_frequency: 0.2,
+            wave_length: 0.6,
+            amplitude: 0.08,
+        }
+    }
+}
+
+impl Material for FlagMaterial {
+    fn vertex_shader() -> ShaderRef {
+        "shaders/flag.wgsl".into()
+    }
+    fn fragment_shader() -> ShaderRef {
+        "shaders/flag.wgsl".into()
+    }
+    fn specialize(
+        _pipeline: &MaterialPipeline<Self>,
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &MeshVertexBufferLayout, // an entitys layout
+        _key: MaterialPipelineKey<Self>, // an entitys key
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        // cloth has two sides
+        descriptor.primitive.cull_mode = None;
+        Ok(())
+    }
+}
+
+impl MaterialBindings for FlagMaterial {
+    const BINDINGS: &'static [(u32, BindingKind)] = &[(0, BindingKind::Uniform)];
+}
@@ -0,0 +1,395 @@
+use bevy::{
+    prelude::*,
+    render::{mesh::VertexAttributeValues, render_resource::PrimitiveTopology},
+    utils::HashMap,
+};
+
+/// Vertex and triangle counts for an entity's mesh, with the number of problems
+/// [`validate_mesh`] found, so they can be read in the world inspector.
+#[derive(Component, Reflect, Default, Debug)]
+#[reflect(Component)]
+pub struct MeshStats {
+    pub vertices: usize,
+    pub triangles: usize,
+    pub problems: usize,
+}
+
+/// Keeps [`MeshStats`] up to date on every entity with a mesh and logs meshes that fail validation.
+pub struct MeshStatsPlugin;
+
+impl Plugin for MeshStatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<MeshStats>()
+            .add_system_to_stage(CoreStage::PostUpdate, update_mesh_stats);
+    }
+}
+
+/// Triangles smaller than this, twice their area really, count as degenerate.
+pub const DEGENERATE_AREA: f32 = 1e-9;
+
+/// Everything [`validate_mesh`] checks for.
+#[derive(Default, Debug, Clone)]
+pub struct MeshReport {
+    pub vertex_count: usize,
+    pub triangle_count: usize,
+    /// Indices that don't point at a vertex.
+    pub out_of_range_indices: usize,
+    /// Triangles with a repeated index or no area.
+    pub degenerate_triangles: usize,
+    /// Normals more than a little off unit length.
+    pub non_unit_normals: usize,
+    /// NaN or infinite values in any float attribute.
+    pub non_finite_values: usize,
+    /// Edges used twice in the same direction, which means neighbouring triangles face opposite ways.
+    pub inconsistent_winding_edges: usize,
+    /// Attributes with a different length than the positions, as `(name, length)`.
+    pub attribute_length_mismatches: Vec<(String, usize)>,
+    pub missing_positions: bool,
+}
+
+impl MeshReport {
+    pub fn is_valid(&self) -> bool {
+        self.problem_count() == 0
+    }
+
+    pub fn problem_count(&self) -> usize {
+        self.out_of_range_indices
+            + self.degenerate_triangles
+            + self.non_unit_normals
+            + self.non_finite_values
+            + self.inconsistent_winding_edges
+            + self.attribute_length_mismatches.len()
+            + self.missing_positions as usize
+    }
+}
+
+/// Checks a triangle list mesh for the mistakes that are easy to make in a generator.
+pub fn validate_mesh(mesh: &Mesh) -> MeshReport {
+    let mut report = MeshReport::default();
+
+    let positions = match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+        Some(VertexAttributeValues::Float32x3(positions)) => positions.as_slice(),
+        _ => {
+            report.missing_positions = true;
+            return report;
+        }
+    };
+    report.vertex_count = positions.len();
+
+    for (id, values) in mesh.attributes() {
+        if values.len() != positions.len() {
+            report
+                .attribute_length_mismatches
+                .push((format!("{:?}", id), values.len()));
+        }
+        report.non_finite_values += match values {
+            VertexAttributeValues::Float32(values) => {
+                values.iter().filter(|v| !v.is_finite()).count()
+            }
+            VertexAttributeValues::Float32x2(values) => count_non_finite(values),
+            VertexAttributeValues::Float32x3(values) => count_non_finite(values),
+            VertexAttributeValues::Float32x4(values) => count_non_finite(values),
+            _ => 0,
+        };
+    }
+
+    if let Some(VertexAttributeValues::Float32x3(normals)) = mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+    {
+        report.non_unit_normals = normals
+            .iter()
+            .filter(|n| (Vec3::from(**n).length() - 1.0).abs() > 1e-3)
+            .count();
+    }
+
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return report;
+    }
+
+    let indices: Vec<usize> = match mesh.indices() {
+        Some(indices) => indices.iter().collect(),
+        None => (0..positions.len()).collect(),
+    };
+    report.triangle_count = indices.len() / 3;
+
+    let mut directed_edges: HashMap<(usize, usize), usize> = HashMap::default();
+    for triangle in indices.chunks_exact(3) {
+        let (a, b, c) = (triangle[0], triangle[1], triangle[2]);
+        let out_of_range = triangle.iter().filter(|&&i| i >= positions.len()).count();
+        if out_of_range > 0 {
+            report.out_of_range_indices += out_of_range;
+            continue;
+        }
+
+        let (pa, pb, pc) = (
+            Vec3::from(positions[a]),
+            Vec3::from(positions[b]),
+            Vec3::from(positions[c]),
+        );
+        let area = (pb - pa).cross(pc - pa).length();
+        if a == b || b == c || c == a || area <= DEGENERATE_AREA {
+            report.degenerate_triangles += 1;
+            continue;
+        }
+
+        for edge in [(a, b), (b, c), (c, a)] {
+            *directed_edges.entry(edge).or_insert(0) += 1;
+        }
+    }
+    report.inconsistent_winding_edges = directed_edges.values().filter(|&&count| count > 1).count();
+
+    report
+}
+
+fn count_non_finite<const N: usize>(values: &[[f32; N]]) -> usize {
+    values
+        .iter()
+        .filter(|v| v.iter().any(|x| !x.is_finite()))
+        .count()
+}
+
+fn update_mesh_stats(
+    mut commands: Commands,
+    meshes: Res<Assets<Mesh>>,
+    mut mesh_events: EventReader<AssetEvent<Mesh>>,
+    query: Query<(Entity, &Handle<Mesh>, Option<&Name>)>,
+    changed: Query<Entity, Changed<Handle<Mesh>>>,
+) {
+    let modified: Vec<&Handle<Mesh>> = mesh_events
+        .iter()
+        .filter_map(|event| match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => Some(handle),
+            AssetEvent::Removed { .. } => None,
+        })
+        .collect();
+    if modified.is_empty() && changed.is_empty() {
+        return;
+    }
+
+    for (entity, handle, name) in query.iter() {
+        if !modified.contains(&handle) && !changed.contains(entity) {
+            continue;
+        }
+        let Some(mesh) = meshes.get(handle) else {
+            continue;
+        };
+        let report = validate_mesh(mesh);
+        if !report.is_valid() {
+            warn!(
+                "mesh of {} has problems: {:?}",
+                name.map_or_else(|| format!("{:?}", entity), |name| name.to_string()),
+                report
+            );
+        }
+        commands.entity(entity).insert(MeshStats {
+            vertices: report.vertex_count,
+            triangles: report.triangle_count,
+            problems: report.problem_count(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::render::mesh::Indices;
+
+    use super::validate_mesh;
+    use crate::capsule::Capsule;
+    use crate::cone::Cone;
+    use crate::cube_sphere::CubeSphere;
+    use crate::cylinder::Cylinder;
+    use crate::disc::Disc;
+    use crate::icosphere::Icosphere;
+    use crate::parametric::ParametricSurface;
+    use crate::plane::SubdividedPlane;
+    use crate::tube::Tube;
+    use crate::vertex_colors::VertexColors;
+
+    fn assert_valid(name: &str, mesh: Mesh) {
+        let report = validate_mesh(&mesh);
+        assert!(report.is_valid(), "{name} has problems: {report:?}");
+    }
+
+    #[test]
+    fn cylinder() {
+        assert_valid("default cylinder", Cylinder::default().into());
+        assert_valid(
+            "smallest cylinder",
+            Cylinder {
+                resolution: 3,
+                segments: 1,
+                ..default()
+            }
+            .into(),
+        );
+        assert_valid(
+            "cylinder with face colors",
+            Cylinder {
+                colors: VertexColors::RandomPerFace { seed: 7 },
+                ..default()
+            }
+            .into(),
+        );
+    }
+
+    #[test]
+    fn subdivided_plane() {
+        assert_valid("plane", SubdividedPlane::default().into());
+        assert_valid(
+            "subdivided plane",
+            SubdividedPlane {
+                subdivisions: 10,
+                size: 4.0,
+                colors: VertexColors::Height {
+                    low: Color::BLACK,
+                    high: Color::WHITE,
+                },
+            }
+            .into(),
+        );
+    }
+
+    #[test]
+    fn cone() {
+        assert_valid("cone", Cone::default().into());
+        assert_valid(
+            "frustum",
+            Cone {
+                top_radius: 0.25,
+                resolution: 3,
+                segments: 1,
+                ..default()
+            }
+            .into(),
+        );
+        assert_valid(
+            "upside down cone",
+            Cone {
+                bottom_radius: 0.0,
+                top_radius: 0.5,
+                ..default()
+            }
+            .into(),
+        );
+    }
+
+    #[test]
+    fn capsule() {
+        assert_valid("capsule", Capsule::default().into());
+        assert_valid(
+            "capsule without depth",
+            Capsule {
+                depth: 0.0,
+                resolution: 3,
+                rings: 1,
+                segments: 1,
+                ..default()
+            }
+            .into(),
+        );
+        assert_valid(
+            "long capsule",
+            Capsule {
+                radius: 0.25,
+                depth: 4.0,
+                resolution: 12,
+                rings: 3,
+                segments: 8,
+            }
+            .into(),
+        );
+    }
+
+    #[test]
+    fn tube() {
+        assert_valid("tube", Tube::default().into());
+        assert_valid(
+            "smallest tube",
+            Tube {
+                resolution: 3,
+                segments: 1,
+                ..default()
+            }
+            .into(),
+        );
+        assert_valid(
+            "thin walled tube",
+            Tube {
+                radius: 1.0,
+                thickness: 0.01,
+                height: 3.0,
+                resolution: 48,
+                segments: 5,
+            }
+            .into(),
+        );
+    }
+
+    #[test]
+    fn disc() {
+        assert_valid("disc", Disc::default().into());
+        assert_valid(
+            "ring",
+            Disc {
+                inner_radius: 0.25,
+                resolution: 3,
+                rings: 1,
+                ..default()
+            }
+            .into(),
+        );
+    }
+
+    #[test]
+    fn icosphere() {
+        for subdivisions in 0..4 {
+            let sphere = Icosphere {
+                subdivisions,
+                ..default()
+            };
+            assert_valid(&format!("{sphere:?}"), sphere.into());
+        }
+    }
+
+    #[test]
+    fn cube_sphere() {
+        for subdivisions in [0, 1, 8] {
+            let sphere = CubeSphere {
+                subdivisions,
+                ..default()
+            };
+            assert_valid(&format!("{sphere:?}"), sphere.into());
+        }
+    }
+
+    #[test]
+    fn parametric_presets() {
+        assert_valid("torus", ParametricSurface::torus(0.4, 0.15, 32).into());
+        assert_valid(
+            "mobius strip",
+            ParametricSurface::mobius(0.4, 0.3, 64).into(),
+        );
+        assert_valid(
+            "klein bottle",
+            ParametricSurface::klein_bottle(1.0, 48).into(),
+        );
+        assert_valid(
+            "superquadric",
+            ParametricSurface::superquadric(0.5, 0.3, 0.3, 32).into(),
+        );
+        // exponents of 1 pinch the poles to a point
+        assert_valid(
+            "superquadric sphere",
+            ParametricSurface::superquadric(0.5, 1.0, 1.0, 16).into(),
+        );
+    }
+
+    #[test]
+    fn catches_bad_indices() {
+        let mut mesh: Mesh = SubdividedPlane::default().into();
+        mesh.set_indices(Some(Indices::U32(vec![0, 1, 9, 0, 0, 1])));
+        let report = validate_mesh(&mesh);
+        assert_eq!(report.out_of_range_indices, 1);
+        assert_eq!(report.degenerate_triangles, 1);
+    }
+}
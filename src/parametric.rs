@@ -0,0 +1,204 @@
+use std::f32::consts::{PI, TAU};
+
+use bevy::{
+    prelude::{Mesh, Vec3},
+    render::{mesh::Indices, render_resource::PrimitiveTopology},
+};
+
+use crate::mesh_validation::DEGENERATE_AREA;
+
+/// Any surface described by a function from `(u, v)`, both in `0.0..=1.0`, to a position.
+/// Normals come from `normal` when given, otherwise from finite differences of `position`.
+///
+/// Möbius strips and Klein bottles have no outside, render them without back face culling.
+pub struct ParametricSurface {
+    pub position: Box<dyn Fn(f32, f32) -> Vec3>,
+    /// Analytic normal, pointing to the side that should face the camera.
+    pub normal: Option<Box<dyn Fn(f32, f32) -> Vec3>>,
+    /// Number of quads along u.
+    pub u_resolution: u32,
+    /// Number of quads along v.
+    pub v_resolution: u32,
+    /// The surface continues past u = 1, e.g. around a torus,
+    /// so finite differences at the edge can look across it.
+    pub wrap_u: bool,
+    /// As `wrap_u` for v.
+    pub wrap_v: bool,
+}
+
+impl ParametricSurface {
+    pub fn new(
+        u_resolution: u32,
+        v_resolution: u32,
+        position: impl Fn(f32, f32) -> Vec3 + 'static,
+    ) -> Self {
+        Self {
+            position: Box::new(position),
+            normal: None,
+            u_resolution,
+            v_resolution,
+            wrap_u: false,
+            wrap_v: false,
+        }
+    }
+
+    pub fn with_normal(mut self, normal: impl Fn(f32, f32) -> Vec3 + 'static) -> Self {
+        self.normal = Some(Box::new(normal));
+        self
+    }
+
+    pub fn with_wrap(mut self, wrap_u: bool, wrap_v: bool) -> Self {
+        self.wrap_u = wrap_u;
+        self.wrap_v = wrap_v;
+        self
+    }
+
+    /// A torus lying on the XZ plane like [`bevy::prelude::shape::Torus`].
+    pub fn torus(radius: f32, ring_radius: f32, resolution: u32) -> Self {
+        let position = move |u: f32, v: f32| {
+            let (sin_u, cos_u) = (u * TAU).sin_cos();
+            let (sin_v, cos_v) = (v * TAU).sin_cos();
+            let distance = radius + ring_radius * cos_v;
+            Vec3::new(distance * cos_u, ring_radius * sin_v, distance * sin_u)
+        };
+        let normal = |u: f32, v: f32| {
+            let (sin_u, cos_u) = (u * TAU).sin_cos();
+            let (sin_v, cos_v) = (v * TAU).sin_cos();
+            Vec3::new(cos_v * cos_u, sin_v, cos_v * sin_u)
+        };
+        Self::new(resolution, resolution / 2, position)
+            .with_normal(normal)
+            .with_wrap(true, true)
+    }
+
+    /// A Möbius strip of the given radius and width.
+    pub fn mobius(radius: f32, width: f32, resolution: u32) -> Self {
+        let position = move |u: f32, v: f32| {
+            let angle = u * TAU;
+            let offset = (v - 0.5) * width;
+            let (sin, cos) = angle.sin_cos();
+            let (half_sin, half_cos) = (angle / 2.0).sin_cos();
+            let distance = radius + offset * half_cos;
+            Vec3::new(distance * cos, offset * half_sin, distance * sin)
+        };
+        Self::new(resolution, (resolution / 8).max(1), position).with_wrap(true, false)
+    }
+
+    /// The figure eight immersion of a Klein bottle, `radius` is the size of the loop.
+    pub fn klein_bottle(radius: f32, resolution: u32) -> Self {
+        let position = move |u: f32, v: f32| {
+            let (u, v) = (u * TAU, v * TAU);
+            let sin_v = v.sin();
+            let sin_2v = (2.0 * v).sin();
+            let (half_sin, half_cos) = (u / 2.0).sin_cos();
+            let distance = radius + half_cos * sin_v - half_sin * sin_2v;
+            Vec3::new(
+                distance * u.cos(),
+                half_sin * sin_v + half_cos * sin_2v,
+                distance * u.sin(),
+            ) * 0.5
+        };
+        Self::new(resolution, resolution / 2, position).with_wrap(true, true)
+    }
+
+    /// A superellipsoid, exponents of 1 make a sphere, small ones a rounded box and large ones a star.
+    pub fn superquadric(radius: f32, east_west: f32, north_south: f32, resolution: u32) -> Self {
+        // sign preserving power, keeps each octant on its own side
+        let power = |x: f32, exponent: f32| x.signum() * x.abs().powf(exponent);
+        let position = move |u: f32, v: f32| {
+            let longitude = u * TAU - PI;
+            let latitude = v * PI - PI / 2.0;
+            let (sin_lat, cos_lat) = latitude.sin_cos();
+            let (sin_lon, cos_lon) = longitude.sin_cos();
+            Vec3::new(
+                power(cos_lat, north_south) * power(cos_lon, east_west),
+                power(sin_lat, north_south),
+                power(cos_lat, north_south) * power(sin_lon, east_west),
+            ) * radius
+        };
+        Self::new(resolution, resolution / 2, position).with_wrap(true, false)
+    }
+
+    fn finite_difference_normal(&self, u: f32, v: f32, step_u: f32, step_v: f32) -> Vec3 {
+        // central differences, one sided at the edge of a surface that doesn't wrap
+        let (u0, u1) = if self.wrap_u {
+            (u - step_u, u + step_u)
+        } else {
+            ((u - step_u).max(0.0), (u + step_u).min(1.0))
+        };
+        let (v0, v1) = if self.wrap_v {
+            (v - step_v, v + step_v)
+        } else {
+            ((v - step_v).max(0.0), (v + step_v).min(1.0))
+        };
+        let du = (self.position)(u1, v) - (self.position)(u0, v);
+        let dv = (self.position)(u, v1) - (self.position)(u, v0);
+        // the index winding below is front facing for dv x du
+        dv.cross(du).normalize_or_zero()
+    }
+}
+
+impl From<ParametricSurface> for Mesh {
+    fn from(surface: ParametricSurface) -> Self {
+        debug_assert!(surface.u_resolution > 0);
+        debug_assert!(surface.v_resolution > 0);
+
+        let u_vertex_count = surface.u_resolution + 1;
+        let v_vertex_count = surface.v_resolution + 1;
+        let num_vertices = (u_vertex_count * v_vertex_count) as usize;
+        let num_indices = (surface.u_resolution * surface.v_resolution * 6) as usize;
+
+        let mut positions: Vec<[f32; 3]> = Vec::with_capacity(num_vertices);
+        let mut normals: Vec<[f32; 3]> = Vec::with_capacity(num_vertices);
+        let mut uvs: Vec<[f32; 2]> = Vec::with_capacity(num_vertices);
+        let mut indices: Vec<u32> = Vec::with_capacity(num_indices);
+
+        // a fraction of a quad so the differences stay local
+        let step_u = 0.25 / surface.u_resolution as f32;
+        let step_v = 0.25 / surface.v_resolution as f32;
+
+        for y in 0..v_vertex_count {
+            for x in 0..u_vertex_count {
+                let u = x as f32 / surface.u_resolution as f32;
+                let v = y as f32 / surface.v_resolution as f32;
+                let mut normal = match &surface.normal {
+                    Some(normal) => normal(u, v).normalize_or_zero(),
+                    None => surface.finite_difference_normal(u, v, step_u, step_v),
+                };
+                // poles collapse a whole row to one point, look a little way in instead
+                if normal == Vec3::ZERO {
+                    let inward_v = if v < 0.5 { v + step_v } else { v - step_v };
+                    normal = surface.finite_difference_normal(u, inward_v, step_u, step_v);
+                }
+
+                positions.push((surface.position)(u, v).to_array());
+                normals.push(normal.to_array());
+                uvs.push([u, 1.0 - v]);
+            }
+        }
+
+        // poles collapse a whole row to one point, leave out the triangles without area there
+        let mut push_triangle = |triangle: [u32; 3]| {
+            let [a, b, c] = triangle.map(|i| Vec3::from(positions[i as usize]));
+            if (b - a).cross(c - a).length() > DEGENERATE_AREA {
+                indices.extend_from_slice(&triangle);
+            }
+        };
+        for y in 0..v_vertex_count - 1 {
+            for x in 0..u_vertex_count - 1 {
+                let quad = y * u_vertex_count + x;
+                push_triangle([quad + u_vertex_count + 1, quad + 1, quad + u_vertex_count]);
+                push_triangle([quad, quad + u_vertex_count, quad + 1]);
+            }
+        }
+
+        assert_eq!(num_vertices, positions.len());
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.set_indices(Some(Indices::U32(indices)));
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        mesh
+    }
+}
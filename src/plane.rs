@@ -1,14 +1,29 @@
 use bevy::{
-    prelude::{Mesh, Vec3},
+    prelude::{Component, Mesh, Reflect, ReflectComponent, Vec3},
     render::{mesh::Indices, render_resource::PrimitiveTopology},
 };
 
+use crate::vertex_colors::VertexColors;
+
 // bevy/crates/bevy_render/src/mesh/shape
 // https://answers.unity.com/questions/1850185/mesh-triangles-not-filling-whole-space-2.html
 
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component)]
 pub struct SubdividedPlane {
     pub subdivisions: u32,
     pub size: f32,
+    pub colors: VertexColors,
+}
+
+impl Default for SubdividedPlane {
+    fn default() -> Self {
+        Self {
+            subdivisions: 0,
+            size: 1.0,
+            colors: VertexColors::None,
+        }
+    }
 }
 
 impl From<SubdividedPlane> for Mesh {
@@ -56,6 +71,7 @@ impl From<SubdividedPlane> for Mesh {
         mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
         mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
         mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        plane.colors.apply(&mut mesh);
         mesh
     }
 }
@@ -0,0 +1,361 @@
+//! `--check-shaders` validates every material's shaders offline, no window or GPU needed.
+//! Bevy's own shaders are registered by the plugins as they build, which is enough
+//! for the `#import`s to resolve, then naga parses and validates the result.
+
+use std::{hash::Hash, path::Path};
+
+use bevy::{
+    asset::{AssetPlugin, HandleId},
+    prelude::*,
+    render::{
+        render_resource::{
+            ProcessedShader, ShaderImport, ShaderProcessor, ShaderRef, ShaderReflectError,
+            ShaderStage, WgpuFeatures,
+        },
+        settings::WgpuSettings,
+    },
+    utils::HashMap,
+    winit::WinitPlugin,
+};
+
+use crate::materials::{visit_materials, BindingKind, MaterialBindings, MaterialVisitor};
+
+/// Checks every shader and exits with 1 if any has a problem.
+pub fn check_shaders() -> ! {
+    let problems = check_materials();
+    if problems == 0 {
+        println!("all shaders ok");
+        std::process::exit(0);
+    }
+    println!("{} shader problems", problems);
+    std::process::exit(1);
+}
+
+/// Checks the shaders of every material, returns the number of problems.
+fn check_materials() -> usize {
+    let mut app = App::new();
+    // no backends, the render app isn't made but the plugins still register bevy's shaders
+    app.insert_resource(WgpuSettings {
+        backends: None,
+        ..default()
+    })
+    .add_plugins(
+        DefaultPlugins
+            .set(AssetPlugin {
+                asset_folder: "assets".to_string(),
+                ..default()
+            })
+            .disable::<WinitPlugin>(),
+    );
+
+    let mut checker = ShaderChecker::new(app.world.resource::<Assets<Shader>>());
+    visit_materials(&mut checker);
+    checker.problems
+}
+
+struct ShaderChecker {
+    shaders: HashMap<Handle<Shader>, Shader>,
+    import_handles: HashMap<ShaderImport, Handle<Shader>>,
+    problems: usize,
+}
+
+impl MaterialVisitor for ShaderChecker {
+    fn visit<M: Material + MaterialBindings>(&mut self, glsl: &[&'static str])
+    where
+        M::Data: PartialEq + Eq + Hash + Clone,
+    {
+        self.check_material::<M>(glsl);
+    }
+}
+
+impl ShaderChecker {
+    fn new(assets: &Assets<Shader>) -> Self {
+        let mut shaders = HashMap::default();
+        let mut import_handles = HashMap::default();
+        for (id, shader) in assets.iter() {
+            let handle = Handle::weak(id);
+            if let Some(import) = shader.import_path() {
+                import_handles.insert(import.clone(), handle.clone());
+            }
+            shaders.insert(handle, shader.clone());
+        }
+        Self {
+            shaders,
+            import_handles,
+            problems: 0,
+        }
+    }
+
+    fn problem(&mut self, path: &str, message: impl std::fmt::Display) {
+        println!("error {}: {}", path, message);
+        self.problems += 1;
+    }
+
+    fn check_material<M: Material + MaterialBindings>(&mut self, glsl: &[&str]) {
+        let name = std::any::type_name::<M>().rsplit("::").next().unwrap();
+        let bindings = M::BINDINGS;
+
+        let mut paths: Vec<String> = Vec::new();
+        for shader in [M::vertex_shader(), M::fragment_shader()] {
+            if let ShaderRef::Path(path) = shader {
+                paths.push(path.path().to_string_lossy().to_string());
+            }
+        }
+        paths.extend(glsl.iter().map(|path| path.to_string()));
+        paths.sort();
+        paths.dedup();
+
+        for path in paths {
+            let Some(used) = self.check_file(&path) else {
+                continue;
+            };
+            let problems = self.problems;
+            for (group, binding, kind) in used {
+                // group 0 is the view and group 2 the mesh, only group 1 comes from the material
+                if group != 1 {
+                    continue;
+                }
+                match bindings.iter().find(|(index, _)| *index == binding) {
+                    None => self.problem(
+                        &path,
+                        format!(
+                            "@group(1) @binding({}) isn't a binding of {}",
+                            binding, name
+                        ),
+                    ),
+                    Some((_, declared)) if *declared != kind => self.problem(
+                        &path,
+                        format!(
+                            "binding {} is a {:?} in {} but a {:?} in the shader",
+                            binding, declared, name, kind
+                        ),
+                    ),
+                    Some(_) => {}
+                }
+            }
+            if self.problems == problems {
+                println!("ok    {} ({})", path, name);
+            }
+        }
+    }
+
+    /// Reads the files imported by their path, like `common.wgsl`, the headless app never loads them.
+    fn load_asset_imports(&mut self, shader: &Shader) {
+        for import in shader.imports() {
+            let ShaderImport::AssetPath(path) = import else {
+                continue;
+            };
+            if self.import_handles.contains_key(import) {
+                continue;
+            }
+            // a missing file shows up as an unresolved import when processing
+            let Ok(source) = std::fs::read_to_string(Path::new("assets").join(path)) else {
+                continue;
+            };
+            let imported = Shader::from_wgsl(source);
+            let handle: Handle<Shader> = Handle::weak(HandleId::from(path.as_str()));
+            self.import_handles.insert(import.clone(), handle.clone());
+            self.shaders.insert(handle, imported.clone());
+            self.load_asset_imports(&imported);
+        }
+    }
+
+    /// Preprocesses and validates a shader, returns the `(group, binding, kind)` of its resources.
+    fn check_file(&mut self, path: &str) -> Option<Vec<(u32, u32, BindingKind)>> {
+        let source = match std::fs::read_to_string(Path::new("assets").join(path)) {
+            Ok(source) => source,
+            Err(error) => {
+                self.problem(path, format!("can't read the file, {}", error));
+                return None;
+            }
+        };
+        let shader = match Path::new(path).extension().and_then(|ext| ext.to_str()) {
+            Some("vert") => Shader::from_glsl(source.clone(), ShaderStage::Vertex),
+            Some("frag") => Shader::from_glsl(source.clone(), ShaderStage::Fragment),
+            _ => Shader::from_wgsl(source.clone()),
+        };
+        self.load_asset_imports(&shader);
+
+        // once without any defs and once with every def the file tests for
+        let defs: Vec<String> = source
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim();
+                line.strip_prefix("#ifdef ")
+                    .or_else(|| line.strip_prefix("#ifndef "))
+                    .map(|def| def.trim().to_string())
+            })
+            .collect();
+
+        let mut used = Vec::new();
+        for defs in [Vec::new(), defs] {
+            let processed = match ShaderProcessor::default().process(
+                &shader,
+                &defs,
+                &self.shaders,
+                &self.import_handles,
+            ) {
+                Ok(processed) => processed,
+                Err(error) => {
+                    self.problem(path, error);
+                    return None;
+                }
+            };
+            let reflection = match processed.reflect(WgpuFeatures::empty()) {
+                Ok(reflection) => reflection,
+                Err(error) => {
+                    let message = match &processed {
+                        ProcessedShader::Wgsl(source) => match &error {
+                            ShaderReflectError::WgslParse(error) => error.emit_to_string(source),
+                            error => error.to_string(),
+                        },
+                        _ => error.to_string(),
+                    };
+                    self.problem(path, format!("with defs {:?}\n{}", defs, message));
+                    return None;
+                }
+            };
+
+            let module = &reflection.module;
+            for (_, variable) in module.global_variables.iter() {
+                let Some(binding) = &variable.binding else {
+                    continue;
+                };
+                let kind = match variable.space {
+                    naga::AddressSpace::Uniform => BindingKind::Uniform,
+                    naga::AddressSpace::Storage { .. } => BindingKind::Storage,
+                    _ => match module.types[variable.ty].inner {
+                        naga::TypeInner::Sampler { .. } => BindingKind::Sampler,
+                        _ => BindingKind::Texture,
+                    },
+                };
+                let entry = (binding.group, binding.binding, kind);
+                if !used.contains(&entry) {
+                    used.push(entry);
+                }
+            }
+        }
+        Some(used)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{hash::Hash, sync::Arc};
+
+    use bevy::{
+        prelude::*,
+        render::{
+            render_resource::{
+                BindGroupDescriptor, BindGroupEntry, BindingResource, BufferDescriptor,
+                BufferUsages, Extent3d, FilterMode, SamplerDescriptor, TextureDescriptor,
+                TextureDimension, TextureFormat, TextureUsages,
+            },
+            renderer::RenderDevice,
+        },
+    };
+    use futures_lite::future::block_on;
+
+    use super::check_materials;
+    use crate::materials::{visit_materials, BindingKind, MaterialBindings, MaterialVisitor};
+
+    #[test]
+    fn shaders_are_valid() {
+        assert_eq!(check_materials(), 0, "run with --check-shaders for details");
+    }
+
+    /// Any adapter will do, even a software one. `None` on machines without one.
+    fn render_device() -> Option<RenderDevice> {
+        let instance = wgpu::Instance::new(wgpu::Backends::all());
+        let adapter = block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()))?;
+        let (device, _queue) = block_on(adapter.request_device(
+            &wgpu::DeviceDescriptor {
+                label: None,
+                features: wgpu::Features::empty(),
+                limits: adapter.limits(),
+            },
+            None,
+        ))
+        .ok()?;
+        Some(RenderDevice::from(Arc::new(device)))
+    }
+
+    /// Builds a bind group from a resource for every entry of `BINDINGS` against the layout
+    /// the `AsBindGroup` derive makes, wgpu refuses it if the two disagree.
+    struct CompareBindings {
+        device: RenderDevice,
+        mismatches: Vec<String>,
+    }
+
+    impl MaterialVisitor for CompareBindings {
+        fn visit<M: Material + MaterialBindings>(&mut self, _glsl: &[&'static str])
+        where
+            M::Data: PartialEq + Eq + Hash + Clone,
+        {
+            let device = self.device.wgpu_device();
+            let layout = M::bind_group_layout(&self.device);
+
+            let buffer = device.create_buffer(&BufferDescriptor {
+                label: None,
+                size: 4096,
+                usage: BufferUsages::UNIFORM | BufferUsages::STORAGE,
+                mapped_at_creation: false,
+            });
+            let texture = device
+                .create_texture(&TextureDescriptor {
+                    label: None,
+                    size: Extent3d::default(),
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: TextureDimension::D2,
+                    format: TextureFormat::Rgba8Unorm,
+                    usage: TextureUsages::TEXTURE_BINDING,
+                })
+                .create_view(&default());
+            let sampler = device.create_sampler(&SamplerDescriptor {
+                mag_filter: FilterMode::Linear,
+                min_filter: FilterMode::Linear,
+                ..default()
+            });
+
+            let entries: Vec<BindGroupEntry> = M::BINDINGS
+                .iter()
+                .map(|&(binding, kind)| BindGroupEntry {
+                    binding,
+                    resource: match kind {
+                        BindingKind::Uniform | BindingKind::Storage => {
+                            BindingResource::Buffer(buffer.as_entire_buffer_binding())
+                        }
+                        BindingKind::Texture => BindingResource::TextureView(&texture),
+                        BindingKind::Sampler => BindingResource::Sampler(&sampler),
+                    },
+                })
+                .collect();
+
+            device.push_error_scope(wgpu::ErrorFilter::Validation);
+            device.create_bind_group(&BindGroupDescriptor {
+                label: None,
+                layout: &layout,
+                entries: &entries,
+            });
+            if let Some(error) = block_on(device.pop_error_scope()) {
+                self.mismatches
+                    .push(format!("{}: {}", std::any::type_name::<M>(), error));
+            }
+        }
+    }
+
+    #[test]
+    fn bindings_match_the_derive() {
+        let Some(device) = render_device() else {
+            println!("no adapter, skipping the binding comparison");
+            return;
+        };
+        let mut compare = CompareBindings {
+            device,
+            mismatches: Vec::new(),
+        };
+        visit_materials(&mut compare);
+        assert!(compare.mismatches.is_empty(), "{:#?}", compare.mismatches);
+    }
+}
@@ -0,0 +1,287 @@
+use std::sync::{Arc, Mutex};
+
+use bevy::{
+    asset::HandleId,
+    prelude::*,
+    render::{
+        render_resource::{
+            AsModuleDescriptorError, CachedPipelineState, PipelineCache, PipelineCacheError,
+            PipelineDescriptor, ProcessedShader, ShaderReflectError,
+        },
+        RenderApp, RenderStage,
+    },
+    utils::{HashMap, HashSet},
+};
+use bevy_inspector_egui::bevy_egui::{egui, EguiContext};
+
+/// Shows shaders that fail to compile after a hot reload in an egui window,
+/// and puts the last version that compiled back so the meshes keep drawing.
+/// Add after `DefaultPlugins`, it needs the render app.
+pub struct ShaderErrorPlugin;
+
+impl Plugin for ShaderErrorPlugin {
+    fn build(&self, app: &mut App) {
+        let reports = ShaderReports::default();
+
+        app.insert_resource(reports.clone())
+            .init_resource::<ShaderErrors>()
+            .add_system(track_shader_errors)
+            .add_system(shader_error_window);
+
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .insert_resource(reports)
+                .add_system_to_stage(RenderStage::Cleanup, report_pipeline_errors);
+        }
+    }
+}
+
+/// A shader that didn't compile.
+#[derive(Clone, Debug)]
+pub struct ShaderError {
+    pub file: String,
+    /// Line in the file, when the compiler pointed at one.
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+/// The shaders that are failing, by their handle.
+#[derive(Resource, Default)]
+pub struct ShaderErrors {
+    pub errors: HashMap<Handle<Shader>, ShaderError>,
+    /// The source of each shader the last time all its pipelines compiled.
+    last_good: HashMap<Handle<Shader>, Shader>,
+    /// Shaders changed on disk that haven't compiled either way yet.
+    pending: HashSet<Handle<Shader>>,
+    /// Shaders we put back ourselves, their modified event isn't an edit.
+    restoring: HashSet<Handle<Shader>>,
+}
+
+#[derive(Clone, Debug)]
+enum ShaderStatus {
+    Pending,
+    Ok,
+    Failed(ShaderFailure),
+}
+
+impl ShaderStatus {
+    fn rank(&self) -> u8 {
+        match self {
+            ShaderStatus::Ok => 0,
+            ShaderStatus::Pending => 1,
+            ShaderStatus::Failed(_) => 2,
+        }
+    }
+}
+
+/// What the render app can tell about a failure, the line is in the processed source
+/// with the imports pasted in, so the text of the line comes along to find it in the file.
+#[derive(Clone, Debug)]
+struct ShaderFailure {
+    message: String,
+    line: Option<usize>,
+    line_text: Option<String>,
+}
+
+/// Written by the render app after the pipelines are built, read by the main app the next frame.
+#[derive(Resource, Clone, Default)]
+struct ShaderReports(Arc<Mutex<HashMap<HandleId, ShaderStatus>>>);
+
+fn report_pipeline_errors(cache: Res<PipelineCache>, reports: Res<ShaderReports>) {
+    let mut statuses: HashMap<HandleId, ShaderStatus> = HashMap::default();
+
+    for pipeline in cache.pipelines() {
+        let shaders: Vec<HandleId> = match &pipeline.descriptor {
+            PipelineDescriptor::RenderPipelineDescriptor(descriptor) => {
+                std::iter::once(descriptor.vertex.shader.id())
+                    .chain(descriptor.fragment.iter().map(|fragment| fragment.shader.id()))
+                    .collect()
+            }
+            PipelineDescriptor::ComputePipelineDescriptor(descriptor) => {
+                vec![descriptor.shader.id()]
+            }
+        };
+        let status = match &pipeline.state {
+            CachedPipelineState::Queued => ShaderStatus::Pending,
+            CachedPipelineState::Ok(_) => ShaderStatus::Ok,
+            CachedPipelineState::Err(error) => match describe(error) {
+                Some(failure) => ShaderStatus::Failed(failure),
+                None => ShaderStatus::Pending,
+            },
+        };
+
+        // a shader is only ok when every pipeline using it is
+        for id in shaders {
+            let entry = statuses.entry(id).or_insert(ShaderStatus::Ok);
+            if status.rank() > entry.rank() {
+                *entry = status.clone();
+            }
+        }
+    }
+
+    *reports.0.lock().unwrap() = statuses;
+}
+
+/// `None` for the errors the pipeline cache retries by itself.
+fn describe(error: &PipelineCacheError) -> Option<ShaderFailure> {
+    let (message, source, offset) = match error {
+        PipelineCacheError::ShaderNotLoaded(_) | PipelineCacheError::ShaderImportNotYetAvailable => {
+            return None;
+        }
+        PipelineCacheError::AsModuleDescriptorError(error, source) => {
+            let source = match source {
+                ProcessedShader::Wgsl(source) | ProcessedShader::Glsl(source, _) => {
+                    Some(source.as_ref())
+                }
+                ProcessedShader::SpirV(_) => None,
+            };
+            let (message, offset) = match error {
+                AsModuleDescriptorError::ShaderReflectError(ShaderReflectError::WgslParse(
+                    error,
+                )) => (
+                    error.to_string(),
+                    source
+                        .and_then(|source| error.location(source))
+                        .map(|location| location.offset as usize),
+                ),
+                AsModuleDescriptorError::ShaderReflectError(ShaderReflectError::GlslParse(
+                    errors,
+                )) => (
+                    errors
+                        .iter()
+                        .map(|error| error.kind.to_string())
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                    errors
+                        .first()
+                        .and_then(|error| error.meta.to_range())
+                        .map(|range| range.start),
+                ),
+                AsModuleDescriptorError::ShaderReflectError(ShaderReflectError::Validation(
+                    error,
+                )) => (
+                    error.to_string(),
+                    source
+                        .and_then(|source| error.location(source))
+                        .map(|location| location.offset as usize),
+                ),
+                error => (error.to_string(), None),
+            };
+            (message, source, offset)
+        }
+        error => (error.to_string(), None, None),
+    };
+
+    let (line, line_text) = match (source, offset) {
+        (Some(source), Some(offset)) if offset <= source.len() => {
+            let line = source[..offset].matches('\n').count();
+            (Some(line + 1), source.lines().nth(line).map(str::to_string))
+        }
+        _ => (None, None),
+    };
+    Some(ShaderFailure {
+        message,
+        line,
+        line_text,
+    })
+}
+
+fn track_shader_errors(
+    mut errors: ResMut<ShaderErrors>,
+    mut events: EventReader<AssetEvent<Shader>>,
+    mut shaders: ResMut<Assets<Shader>>,
+    reports: Res<ShaderReports>,
+    asset_server: Res<AssetServer>,
+) {
+    for event in events.iter() {
+        match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+                if !errors.restoring.remove(handle) {
+                    errors.pending.insert(handle.clone_weak());
+                }
+            }
+            AssetEvent::Removed { handle } => {
+                errors.pending.remove(handle);
+                errors.errors.remove(handle);
+                errors.last_good.remove(handle);
+            }
+        }
+    }
+
+    let statuses = reports.0.lock().unwrap().clone();
+    let pending: Vec<Handle<Shader>> = errors.pending.iter().cloned().collect();
+    for handle in pending {
+        match statuses.get(&handle.id()) {
+            Some(ShaderStatus::Ok) => {
+                errors.pending.remove(&handle);
+                errors.errors.remove(&handle);
+                if let Some(shader) = shaders.get(&handle) {
+                    errors.last_good.insert(handle, shader.clone());
+                }
+            }
+            Some(ShaderStatus::Failed(failure)) => {
+                errors.pending.remove(&handle);
+                let error = shader_error(&asset_server, &handle, failure);
+                errors.errors.insert(handle.clone(), error);
+
+                // put the last good source back, the pipelines build again from it
+                if let Some(good) = errors.last_good.get(&handle).cloned() {
+                    if let Some(shader) = shaders.get_mut(&handle) {
+                        *shader = good;
+                        errors.restoring.insert(handle);
+                    }
+                }
+            }
+            // not compiled yet, or nothing uses it
+            Some(ShaderStatus::Pending) | None => {}
+        }
+    }
+}
+
+fn shader_error(
+    asset_server: &AssetServer,
+    handle: &Handle<Shader>,
+    failure: &ShaderFailure,
+) -> ShaderError {
+    let file = asset_server
+        .get_handle_path(handle)
+        .map(|path| path.path().to_string_lossy().to_string())
+        .unwrap_or_else(|| format!("{:?}", handle.id()));
+
+    // the processed source has the imports pasted in, look the line up in the file itself
+    let line = failure
+        .line_text
+        .as_ref()
+        .and_then(|text| {
+            let source = std::fs::read_to_string(std::path::Path::new("assets").join(&file)).ok()?;
+            source
+                .lines()
+                .position(|line| line.trim() == text.trim())
+                .map(|index| index + 1)
+        })
+        .or(failure.line);
+
+    ShaderError {
+        file,
+        line,
+        message: failure.message.clone(),
+    }
+}
+
+fn shader_error_window(mut egui_context: ResMut<EguiContext>, errors: Res<ShaderErrors>) {
+    if errors.errors.is_empty() {
+        return;
+    }
+    egui::Window::new("Shader Errors").show(egui_context.ctx_mut(), |ui| {
+        for error in errors.errors.values() {
+            let location = match error.line {
+                Some(line) => format!("{}:{}", error.file, line),
+                None => error.file.clone(),
+            };
+            ui.colored_label(egui::Color32::RED, location);
+            ui.monospace(&error.message);
+            ui.separator();
+        }
+        ui.label("Drawing with the last version that compiled.");
+    });
+}
@@ -0,0 +1,345 @@
+use bevy::{
+    prelude::*,
+    render::{
+        mesh::{Indices, MeshVertexAttributeId, VertexAttributeValues},
+        render_resource::PrimitiveTopology,
+    },
+    utils::HashMap,
+};
+
+use crate::lod::MeshLod;
+
+/// Swaps the mesh of entities with a [`Shading`] for a faceted or welded copy.
+/// Works on any mesh, a generator rebuilding it or a glTF finishing loading gets processed again.
+pub struct ShadingPlugin;
+
+impl Plugin for ShadingPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Shading>()
+            .register_type::<ShadingStyle>()
+            .add_system_to_stage(CoreStage::PostUpdate, apply_shading);
+    }
+}
+
+#[derive(Reflect, FromReflect, Clone, Copy, Debug, PartialEq)]
+pub enum ShadingStyle {
+    /// Every triangle gets its own vertices and its face normal, the low poly look.
+    Flat,
+    /// Vertices closer than `epsilon` are merged and their normals averaged.
+    Smooth { epsilon: f32 },
+}
+
+impl Default for ShadingStyle {
+    fn default() -> Self {
+        ShadingStyle::Flat
+    }
+}
+
+/// Doesn't do anything on an entity with a [`MeshLod`], both want to pick the entity's mesh.
+#[derive(Component, Reflect, Default, Debug)]
+#[reflect(Component)]
+pub struct Shading {
+    pub style: ShadingStyle,
+    /// The mesh before processing.
+    source: Handle<Mesh>,
+    /// The processed copy the entity is showing.
+    output: Handle<Mesh>,
+}
+
+impl Shading {
+    pub fn new(style: ShadingStyle) -> Self {
+        Self {
+            style,
+            ..default()
+        }
+    }
+}
+
+fn apply_shading(
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut query: Query<(&mut Shading, &mut Handle<Mesh>, ChangeTrackers<Shading>), Without<MeshLod>>,
+) {
+    for (mut shading, mut handle, tracker) in query.iter_mut() {
+        // anything but our own copy is a new mesh to process
+        let new_mesh = *handle != shading.output;
+        if !new_mesh && !tracker.is_changed() {
+            continue;
+        }
+        let source = if new_mesh {
+            handle.clone()
+        } else {
+            shading.source.clone()
+        };
+        // glTF meshes may still be loading, try again next frame
+        let Some(mesh) = meshes.get(&source) else {
+            continue;
+        };
+
+        let mut mesh = mesh.clone();
+        match shading.style {
+            ShadingStyle::Flat => flat_shade(&mut mesh),
+            ShadingStyle::Smooth { epsilon } => weld_vertices(&mut mesh, epsilon),
+        }
+        let output = meshes.add(mesh);
+
+        // keep our own bookkeeping from counting as a style change next frame
+        let shading = shading.bypass_change_detection();
+        shading.source = source;
+        shading.output = output.clone();
+        *handle = output;
+    }
+}
+
+/// Splits a mesh into unshared vertices with the normal of their triangle.
+pub fn flat_shade(mesh: &mut Mesh) {
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return;
+    }
+    // compute_flat_normals panics on anything else
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        return;
+    };
+    if !indices_in_range(mesh, positions.len()) {
+        return;
+    }
+    if mesh.indices().is_some() {
+        mesh.duplicate_vertices();
+    }
+    mesh.compute_flat_normals();
+}
+
+/// Merges vertices whose positions and other attributes are within `epsilon`
+/// of each other and averages the normals of every vertex at the same position,
+/// so UV seams stay split but don't show in the lighting. The reverse of [`flat_shade`].
+/// Values are snapped to an `epsilon` grid, so two close values either side of a grid line stay apart.
+/// Leaves the mesh alone unless `epsilon` is positive and finite, anything else would snap
+/// every vertex onto the same few grid points.
+pub fn weld_vertices(mesh: &mut Mesh, epsilon: f32) {
+    if !(epsilon > 0.0 && epsilon.is_finite()) {
+        return;
+    }
+
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        return;
+    };
+    let vertex_count = positions.len();
+    if vertex_count == 0 || !indices_in_range(mesh, vertex_count) {
+        return;
+    }
+    // only the float formats the generators and glTF use are supported
+    if mesh
+        .attributes()
+        .any(|(_, values)| values.len() != vertex_count || !is_float(values))
+    {
+        return;
+    }
+
+    let quantize = |value: f32| (value / epsilon).round() as i32;
+
+    // vertices at the same place
+    let mut clusters: HashMap<[i32; 3], usize> = HashMap::default();
+    let cluster_of: Vec<usize> = positions
+        .iter()
+        .map(|p| {
+            let key = [quantize(p[0]), quantize(p[1]), quantize(p[2])];
+            let next = clusters.len();
+            *clusters.entry(key).or_insert(next)
+        })
+        .collect();
+
+    let mut cluster_normals = vec![Vec3::ZERO; clusters.len()];
+    if let Some(VertexAttributeValues::Float32x3(normals)) = mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+    {
+        for (i, normal) in normals.iter().enumerate() {
+            cluster_normals[cluster_of[i]] += Vec3::from(*normal);
+        }
+    }
+
+    // vertices that only differ in their normal
+    let normal_id = MeshVertexAttributeId::from(Mesh::ATTRIBUTE_NORMAL);
+    let mut merged: HashMap<Vec<i32>, u32> = HashMap::default();
+    let mut kept: Vec<usize> = Vec::new();
+    let remap: Vec<u32> = (0..vertex_count)
+        .map(|i| {
+            let mut key = vec![cluster_of[i] as i32];
+            for (id, values) in mesh.attributes() {
+                if id != normal_id {
+                    key.extend(float_values(values, i).unwrap().iter().map(|&v| quantize(v)));
+                }
+            }
+            *merged.entry(key).or_insert_with(|| {
+                kept.push(i);
+                kept.len() as u32 - 1
+            })
+        })
+        .collect();
+
+    for (id, values) in mesh.attributes_mut() {
+        if id == normal_id {
+            let normals: Vec<[f32; 3]> = kept
+                .iter()
+                .map(|&i| cluster_normals[cluster_of[i]].normalize_or_zero().to_array())
+                .collect();
+            *values = VertexAttributeValues::Float32x3(normals);
+        } else {
+            *values = gather(values, &kept);
+        }
+    }
+
+    let indices: Vec<u32> = match mesh.indices() {
+        Some(indices) => indices.iter().map(|i| remap[i]).collect(),
+        None => remap,
+    };
+    mesh.set_indices(Some(Indices::U32(indices)));
+}
+
+/// Whether every index points at one of the `vertex_count` vertices.
+fn indices_in_range(mesh: &Mesh, vertex_count: usize) -> bool {
+    mesh.indices()
+        .map_or(true, |indices| indices.iter().all(|i| i < vertex_count))
+}
+
+/// The formats [`weld_vertices`] handles.
+fn is_float(values: &VertexAttributeValues) -> bool {
+    matches!(
+        values,
+        VertexAttributeValues::Float32(_)
+            | VertexAttributeValues::Float32x2(_)
+            | VertexAttributeValues::Float32x3(_)
+            | VertexAttributeValues::Float32x4(_)
+    )
+}
+
+/// The floats of vertex `i`, `None` for the formats [`is_float`] rejects.
+fn float_values(values: &VertexAttributeValues, i: usize) -> Option<Vec<f32>> {
+    match values {
+        VertexAttributeValues::Float32(v) => Some(vec![v[i]]),
+        VertexAttributeValues::Float32x2(v) => Some(v[i].to_vec()),
+        VertexAttributeValues::Float32x3(v) => Some(v[i].to_vec()),
+        VertexAttributeValues::Float32x4(v) => Some(v[i].to_vec()),
+        _ => None,
+    }
+}
+
+fn gather(values: &VertexAttributeValues, order: &[usize]) -> VertexAttributeValues {
+    match values {
+        VertexAttributeValues::Float32(v) => {
+            VertexAttributeValues::Float32(order.iter().map(|&i| v[i]).collect())
+        }
+        VertexAttributeValues::Float32x2(v) => {
+            VertexAttributeValues::Float32x2(order.iter().map(|&i| v[i]).collect())
+        }
+        VertexAttributeValues::Float32x3(v) => {
+            VertexAttributeValues::Float32x3(order.iter().map(|&i| v[i]).collect())
+        }
+        VertexAttributeValues::Float32x4(v) => {
+            VertexAttributeValues::Float32x4(order.iter().map(|&i| v[i]).collect())
+        }
+        _ => unreachable!("checked by is_float"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::render::mesh::VertexAttributeValues;
+
+    use super::{flat_shade, weld_vertices};
+    use crate::capsule::Capsule;
+    use crate::mesh_validation::validate_mesh;
+    use crate::plane::SubdividedPlane;
+
+    fn positions(mesh: &Mesh) -> &[[f32; 3]] {
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+            .and_then(VertexAttributeValues::as_float3)
+            .unwrap()
+    }
+
+    #[test]
+    fn flat_shading_gives_every_triangle_its_own_vertices() {
+        let mut mesh: Mesh = Capsule::default().into();
+        let triangles = validate_mesh(&mesh).triangle_count;
+        flat_shade(&mut mesh);
+
+        let report = validate_mesh(&mesh);
+        assert!(report.is_valid(), "{report:?}");
+        assert_eq!(report.triangle_count, triangles);
+        assert_eq!(mesh.count_vertices(), triangles * 3);
+
+        let normals = mesh
+            .attribute(Mesh::ATTRIBUTE_NORMAL)
+            .and_then(VertexAttributeValues::as_float3)
+            .unwrap();
+        for (triangle, normals) in positions(&mesh)
+            .chunks_exact(3)
+            .zip(normals.chunks_exact(3))
+        {
+            let [a, b, c] = [0, 1, 2].map(|i| Vec3::from(triangle[i]));
+            let face = (b - a).cross(c - a).normalize();
+            for &normal in normals {
+                assert!(face.dot(Vec3::from(normal)) > 0.999);
+            }
+        }
+    }
+
+    #[test]
+    fn welding_undoes_flat_shading() {
+        let original: Mesh = SubdividedPlane {
+            subdivisions: 4,
+            ..default()
+        }
+        .into();
+        let mut mesh = original.clone();
+        flat_shade(&mut mesh);
+        weld_vertices(&mut mesh, 1e-4);
+
+        let report = validate_mesh(&mesh);
+        assert!(report.is_valid(), "{report:?}");
+        assert_eq!(mesh.count_vertices(), original.count_vertices());
+        assert_eq!(
+            report.triangle_count,
+            validate_mesh(&original).triangle_count
+        );
+    }
+
+    #[test]
+    fn welding_averages_normals_at_shared_positions() {
+        let mut mesh = Mesh::from(shape::Cube::new(1.0));
+        weld_vertices(&mut mesh, 1e-4);
+
+        let report = validate_mesh(&mesh);
+        assert!(report.is_valid(), "{report:?}");
+        let normals = mesh
+            .attribute(Mesh::ATTRIBUTE_NORMAL)
+            .and_then(VertexAttributeValues::as_float3)
+            .unwrap();
+        // each corner is shared by three faces, so its normal points out of the corner
+        for (&position, &normal) in positions(&mesh).iter().zip(normals) {
+            let corner = Vec3::from(position).normalize();
+            assert!(
+                corner.distance(Vec3::from(normal)) < 1e-5,
+                "{normal:?} at {position:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn welding_ignores_an_unusable_epsilon() {
+        let original: Mesh = SubdividedPlane {
+            subdivisions: 4,
+            ..default()
+        }
+        .into();
+        for epsilon in [0.0, -1.0, f32::NAN, f32::INFINITY] {
+            let mut mesh = original.clone();
+            flat_shade(&mut mesh);
+            let flat_vertices = mesh.count_vertices();
+            weld_vertices(&mut mesh, epsilon);
+            assert_eq!(mesh.count_vertices(), flat_vertices, "epsilon {epsilon}");
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::camera::{ActiveCamera, PanOrbitCamera};
+use crate::materials::MovingTextureMaterial;
+use crate::plane::SubdividedPlane;
+
+/// Keeps a square of ground tiles around the camera focus so the camera can pan forever.
+/// Does nothing until a [`Terrain`] resource is inserted.
+pub struct TerrainPlugin;
+
+impl Plugin for TerrainPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TerrainChunks>()
+            .register_type::<TerrainChunk>()
+            .add_system(update_terrain_chunks);
+    }
+}
+
+/// How the ground is laid out. The moving texture material samples the moss and rock
+/// in world space, so neighbouring tiles line up without any seams.
+#[derive(Resource)]
+pub struct Terrain {
+    pub material: Handle<MovingTextureMaterial>,
+    /// Width of a tile in world units.
+    pub chunk_size: f32,
+    /// Number of tiles kept in each direction from the one under the focus.
+    pub view_distance: i32,
+    pub subdivisions: u32,
+    /// Height of the ground plane.
+    pub height: f32,
+}
+
+/// Marks a ground tile with its grid coordinate.
+#[derive(Component, Reflect, Default, Debug)]
+#[reflect(Component)]
+pub struct TerrainChunk {
+    pub coord: IVec2,
+}
+
+/// Every tile shares one mesh, tiles that leave the view are hidden and reused.
+#[derive(Resource, Default)]
+struct TerrainChunks {
+    mesh: Handle<Mesh>,
+    active: HashMap<IVec2, Entity>,
+    pool: Vec<Entity>,
+}
+
+fn update_terrain_chunks(
+    mut commands: Commands,
+    terrain: Option<Res<Terrain>>,
+    mut chunks: ResMut<TerrainChunks>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    active_camera: Res<ActiveCamera>,
+    cameras: Query<&PanOrbitCamera>,
+    mut tiles: Query<(&mut TerrainChunk, &mut Transform, &mut Visibility)>,
+) {
+    let Some(terrain) = terrain else {
+        return;
+    };
+    // the grid coordinates divide by the size, there is no grid without one
+    if !(terrain.chunk_size > 0.0) {
+        return;
+    }
+    let Some(camera) = active_camera
+        .0
+        .and_then(|entity| cameras.get(entity).ok())
+        .or_else(|| cameras.iter().next())
+    else {
+        return;
+    };
+
+    let chunks = &mut *chunks;
+
+    // tiles despawned from the inspector leave a hole that gets filled again below
+    chunks.active.retain(|_, entity| tiles.contains(*entity));
+
+    // rebuild the shared mesh when the tile shape changes, the tiles already hold the handle
+    if terrain.is_changed() {
+        let mesh = Mesh::from(SubdividedPlane {
+            subdivisions: terrain.subdivisions,
+            size: terrain.chunk_size,
+            ..default()
+        });
+        if meshes.contains(&chunks.mesh) {
+            meshes.set_untracked(&chunks.mesh, mesh);
+        } else {
+            chunks.mesh = meshes.add(mesh);
+        }
+        // the tile positions depend on the size too, move all of them
+        for (_, entity) in chunks.active.drain() {
+            chunks.pool.push(entity);
+        }
+        for &entity in chunks.pool.iter() {
+            if let Ok((_, _, mut visibility)) = tiles.get_mut(entity) {
+                visibility.is_visible = false;
+            }
+        }
+    }
+
+    let center = (camera.focus.xz() / terrain.chunk_size).round().as_ivec2();
+    let in_view = |coord: IVec2| (coord - center).abs().max_element() <= terrain.view_distance;
+
+    // hide the tiles that left the view
+    let gone: Vec<IVec2> = chunks
+        .active
+        .keys()
+        .filter(|&&coord| !in_view(coord))
+        .cloned()
+        .collect();
+    for coord in gone {
+        let entity = chunks.active.remove(&coord).unwrap();
+        if let Ok((_, _, mut visibility)) = tiles.get_mut(entity) {
+            visibility.is_visible = false;
+        }
+        chunks.pool.push(entity);
+    }
+
+    // fill the view, from the pool when there is a spare tile
+    for x in -terrain.view_distance..=terrain.view_distance {
+        for z in -terrain.view_distance..=terrain.view_distance {
+            let coord = center + IVec2::new(x, z);
+            if chunks.active.contains_key(&coord) {
+                continue;
+            }
+            let transform = Transform::from_xyz(
+                coord.x as f32 * terrain.chunk_size,
+                terrain.height,
+                coord.y as f32 * terrain.chunk_size,
+            );
+
+            // pooled tiles may have been despawned too, skip those
+            let mut reused = None;
+            while let Some(entity) = chunks.pool.pop() {
+                let Ok((mut chunk, mut tile_transform, mut visibility)) = tiles.get_mut(entity)
+                else {
+                    continue;
+                };
+                chunk.coord = coord;
+                *tile_transform = transform;
+                visibility.is_visible = true;
+                reused = Some(entity);
+                break;
+            }
+
+            let entity = match reused {
+                Some(entity) => entity,
+                None => commands
+                    .spawn((
+                        MaterialMeshBundle {
+                            mesh: chunks.mesh.clone(),
+                            material: terrain.material.clone(),
+                            transform,
+                            ..default()
+                        },
+                        TerrainChunk { coord },
+                        Name::from("terrain chunk"),
+                    ))
+                    .id(),
+            };
+            chunks.active.insert(coord, entity);
+        }
+    }
+}
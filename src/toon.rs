@@ -0,0 +1,118 @@
+use bevy::{asset::HandleId, pbr::NotShadowCaster, prelude::*, utils::HashMap};
+use bevy_inspector_egui::quick::AssetInspectorPlugin;
+
+use crate::materials::{ToonMaterial, ToonOutlineMaterial};
+
+/// Draws the outline of every mesh with a [`ToonMaterial`] as a second, inverted hull mesh
+/// parented to it, and shows the toon materials in an inspector window.
+/// The material plugins for both materials are added separately with the others.
+pub struct ToonPlugin;
+
+impl Plugin for ToonPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<ToonMaterial>()
+            .add_plugin(AssetInspectorPlugin::<ToonMaterial>::default())
+            .init_resource::<ToonOutlines>()
+            .add_system(update_outline_materials)
+            .add_system(update_outlines.after(update_outline_materials));
+    }
+}
+
+/// The outline material made for each toon material.
+#[derive(Resource, Default)]
+struct ToonOutlines(HashMap<HandleId, Handle<ToonOutlineMaterial>>);
+
+impl ToonOutlines {
+    fn get_or_add(
+        &mut self,
+        toon: &Handle<ToonMaterial>,
+        toons: &Assets<ToonMaterial>,
+        outline_materials: &mut Assets<ToonOutlineMaterial>,
+    ) -> Option<Handle<ToonOutlineMaterial>> {
+        if let Some(outline) = self.0.get(&toon.id()) {
+            return Some(outline.clone());
+        }
+        let outline = outline_materials.add(outline_material(toons.get(toon)?));
+        self.0.insert(toon.id(), outline.clone());
+        Some(outline)
+    }
+}
+
+/// On a mesh with a toon material, the child drawing its outline.
+#[derive(Component)]
+struct OutlinedBy(Entity);
+
+fn outline_material(toon: &ToonMaterial) -> ToonOutlineMaterial {
+    ToonOutlineMaterial {
+        color: toon.outline_color,
+        width: toon.outline_width,
+    }
+}
+
+/// Copies edits to the toon materials, like the ones from the inspector, to their outlines.
+fn update_outline_materials(
+    mut events: EventReader<AssetEvent<ToonMaterial>>,
+    toons: Res<Assets<ToonMaterial>>,
+    mut outline_materials: ResMut<Assets<ToonOutlineMaterial>>,
+    mut outlines: ResMut<ToonOutlines>,
+) {
+    for event in events.iter() {
+        match event {
+            // new materials get their outline when a mesh first uses them
+            AssetEvent::Created { .. } => {}
+            AssetEvent::Modified { handle } => {
+                let (Some(toon), Some(outline)) = (toons.get(handle), outlines.0.get(&handle.id()))
+                else {
+                    continue;
+                };
+                if let Some(material) = outline_materials.get_mut(outline) {
+                    *material = outline_material(toon);
+                }
+            }
+            AssetEvent::Removed { handle } => {
+                outlines.0.remove(&handle.id());
+            }
+        }
+    }
+}
+
+/// Gives new toon meshes an outline child and keeps its mesh the same as the parent's,
+/// generators and [`crate::lod::MeshLod`] swap the mesh after spawning.
+fn update_outlines(
+    mut commands: Commands,
+    mut outlines: ResMut<ToonOutlines>,
+    toons: Res<Assets<ToonMaterial>>,
+    mut outline_materials: ResMut<Assets<ToonOutlineMaterial>>,
+    query: Query<
+        (Entity, &Handle<ToonMaterial>, &Handle<Mesh>, Option<&OutlinedBy>),
+        Or<(Changed<Handle<ToonMaterial>>, Changed<Handle<Mesh>>)>,
+    >,
+) {
+    for (entity, toon, mesh, outlined_by) in query.iter() {
+        let Some(material) = outlines.get_or_add(toon, &toons, &mut outline_materials) else {
+            continue;
+        };
+        match outlined_by {
+            Some(OutlinedBy(child)) => {
+                commands.entity(*child).insert((mesh.clone(), material));
+            }
+            None => {
+                let child = commands
+                    .spawn((
+                        MaterialMeshBundle {
+                            mesh: mesh.clone(),
+                            material,
+                            ..default()
+                        },
+                        NotShadowCaster,
+                        Name::from("outline"),
+                    ))
+                    .id();
+                commands
+                    .entity(entity)
+                    .insert(OutlinedBy(child))
+                    .add_child(child);
+            }
+        }
+    }
+}
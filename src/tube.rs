@@ -0,0 +1,204 @@
+use bevy::{
+    prelude::{Component, Mesh, Reflect, ReflectComponent},
+    render::{mesh::Indices, render_resource::PrimitiveTopology},
+};
+
+/// A hollow cylinder with walls of `thickness`, standing on the XZ plane.
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component)]
+pub struct Tube {
+    /// Outer radius in the XZ plane.
+    pub radius: f32,
+    /// Thickness of the wall, the inner radius is `radius - thickness`.
+    pub thickness: f32,
+    /// Height of the tube in the Y axis.
+    pub height: f32,
+    /// Number of vertices around each horizontal slice of the tube.
+    pub resolution: u32,
+    /// Number of vertical segments.
+    pub segments: u32,
+}
+
+impl Default for Tube {
+    fn default() -> Self {
+        Self {
+            radius: 0.5,
+            thickness: 0.1,
+            height: 1.0,
+            resolution: 16,
+            segments: 4,
+        }
+    }
+}
+
+impl From<Tube> for Mesh {
+    fn from(t: Tube) -> Self {
+        debug_assert!(t.radius > 0.0);
+        debug_assert!(t.thickness > 0.0 && t.thickness < t.radius);
+        debug_assert!(t.height > 0.0);
+        debug_assert!(t.resolution > 2);
+        debug_assert!(t.segments > 0);
+
+        let inner_radius = t.radius - t.thickness;
+        let num_rings = t.segments + 1;
+        let num_vertices = 2 * num_rings * (t.resolution + 1) + 4 * (t.resolution + 1);
+        let num_indices = (2 * t.segments * t.resolution + 2 * t.resolution) * 6;
+
+        let mut positions = Vec::with_capacity(num_vertices as usize);
+        let mut normals = Vec::with_capacity(num_vertices as usize);
+        let mut uvs = Vec::with_capacity(num_vertices as usize);
+        let mut indices = Vec::with_capacity(num_indices as usize);
+
+        let step_theta = std::f32::consts::TAU / t.resolution as f32;
+        let step_y = t.height / t.segments as f32;
+
+        // walls, the inner wall faces the axis
+
+        let mut build_wall = |radius: f32, outside: bool| {
+            let offset = positions.len() as u32;
+            let facing = if outside { 1.0 } else { -1.0 };
+
+            for ring in 0..num_rings {
+                let y = -t.height / 2.0 + ring as f32 * step_y;
+
+                for segment in 0..=t.resolution {
+                    let theta = segment as f32 * step_theta;
+                    let (sin, cos) = theta.sin_cos();
+
+                    positions.push([radius * cos, y, radius * sin]);
+                    normals.push([cos * facing, 0., sin * facing]);
+                    uvs.push([
+                        segment as f32 / t.resolution as f32,
+                        ring as f32 / t.segments as f32,
+                    ]);
+                }
+            }
+
+            for i in 0..t.segments {
+                let ring = offset + i * (t.resolution + 1);
+                let next_ring = offset + (i + 1) * (t.resolution + 1);
+
+                for j in 0..t.resolution {
+                    if outside {
+                        indices.extend_from_slice(&[
+                            ring + j,
+                            next_ring + j,
+                            ring + j + 1,
+                            next_ring + j,
+                            next_ring + j + 1,
+                            ring + j + 1,
+                        ]);
+                    } else {
+                        indices.extend_from_slice(&[
+                            ring + j,
+                            ring + j + 1,
+                            next_ring + j,
+                            next_ring + j,
+                            ring + j + 1,
+                            next_ring + j + 1,
+                        ]);
+                    }
+                }
+            }
+        };
+
+        build_wall(t.radius, true);
+        build_wall(inner_radius, false);
+
+        // rims, flat rings joining the walls at the top and bottom
+
+        let mut build_rim = |top: bool| {
+            let offset = positions.len() as u32;
+            let (y, normal_y) = if top {
+                (t.height / 2., 1.)
+            } else {
+                (t.height / -2., -1.)
+            };
+
+            for radius in [inner_radius, t.radius] {
+                for segment in 0..=t.resolution {
+                    let theta = segment as f32 * step_theta;
+                    let (sin, cos) = theta.sin_cos();
+                    let uv_radius = radius / t.radius;
+
+                    positions.push([radius * cos, y, radius * sin]);
+                    normals.push([0.0, normal_y, 0.0]);
+                    uvs.push([0.5 * (1.0 + cos * uv_radius), 0.5 * (1.0 - sin * uv_radius)]);
+                }
+            }
+
+            let inner = offset;
+            let outer = offset + t.resolution + 1;
+            for j in 0..t.resolution {
+                if top {
+                    indices.extend_from_slice(&[
+                        inner + j,
+                        outer + j + 1,
+                        outer + j,
+                        inner + j,
+                        inner + j + 1,
+                        outer + j + 1,
+                    ]);
+                } else {
+                    indices.extend_from_slice(&[
+                        inner + j,
+                        outer + j,
+                        outer + j + 1,
+                        inner + j,
+                        outer + j + 1,
+                        inner + j + 1,
+                    ]);
+                }
+            }
+        };
+
+        build_rim(true);
+        build_rim(false);
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.set_indices(Some(Indices::U32(indices)));
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        mesh
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::{Mesh, Vec2};
+    use bevy::render::mesh::VertexAttributeValues;
+
+    use super::Tube;
+
+    #[test]
+    fn vertices_lie_on_the_inner_or_outer_wall() {
+        let tubes = [
+            Tube::default(),
+            Tube {
+                radius: 1.0,
+                thickness: 0.01,
+                height: 3.0,
+                resolution: 48,
+                segments: 5,
+            },
+        ];
+        for tube in tubes {
+            let (outer, inner) = (tube.radius, tube.radius - tube.thickness);
+            let half_height = tube.height / 2.0;
+            let mesh = Mesh::from(tube);
+            let positions = mesh
+                .attribute(Mesh::ATTRIBUTE_POSITION)
+                .and_then(VertexAttributeValues::as_float3)
+                .unwrap();
+            for &[x, y, z] in positions {
+                let radius = Vec2::new(x, z).length();
+                assert!(
+                    (radius - outer).abs() < 1e-5 || (radius - inner).abs() < 1e-5,
+                    "{radius} is inside the wall"
+                );
+                assert!(y.abs() <= half_height + 1e-6);
+            }
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use bevy::{
+    prelude::{Color, FromReflect, Mesh, Reflect},
+    render::mesh::VertexAttributeValues,
+};
+
+/// Painted colors a generator can put in `Mesh::ATTRIBUTE_COLOR`,
+/// drawn by [`crate::materials::VertexColorMaterial`].
+#[derive(Reflect, FromReflect, Clone, Debug, Default)]
+pub enum VertexColors {
+    #[default]
+    None,
+    /// Blends from `start` to `end` along the V texture coordinate.
+    Gradient { start: Color, end: Color },
+    /// Blends from `low` at the lowest vertex to `high` at the highest.
+    Height { low: Color, high: Color },
+    /// A different color for every triangle, the vertices are split so the triangles don't share any.
+    RandomPerFace { seed: u32 },
+}
+
+impl VertexColors {
+    /// Adds the colors to a finished mesh, does nothing for [`VertexColors::None`].
+    pub fn apply(&self, mesh: &mut Mesh) {
+        match self {
+            VertexColors::None => {}
+            VertexColors::Gradient { start, end } => {
+                let Some(VertexAttributeValues::Float32x2(uvs)) =
+                    mesh.attribute(Mesh::ATTRIBUTE_UV_0)
+                else {
+                    return;
+                };
+                let colors: Vec<[f32; 4]> =
+                    uvs.iter().map(|uv| lerp(*start, *end, uv[1])).collect();
+                mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+            }
+            VertexColors::Height { low, high } => {
+                let Some(VertexAttributeValues::Float32x3(positions)) =
+                    mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+                else {
+                    return;
+                };
+                let min = positions.iter().map(|p| p[1]).fold(f32::MAX, f32::min);
+                let max = positions.iter().map(|p| p[1]).fold(f32::MIN, f32::max);
+                // a flat mesh is all low
+                let range = (max - min).max(f32::EPSILON);
+                let colors: Vec<[f32; 4]> = positions
+                    .iter()
+                    .map(|p| lerp(*low, *high, (p[1] - min) / range))
+                    .collect();
+                mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+            }
+            VertexColors::RandomPerFace { seed } => {
+                // every triangle gets its own vertices so the colors don't bleed into the neighbours
+                mesh.duplicate_vertices();
+                let vertex_count = mesh.count_vertices();
+                let colors: Vec<[f32; 4]> = (0..vertex_count)
+                    .map(|i| {
+                        let face = (i / 3) as u32;
+                        Color::hsl(hash(face, *seed) * 360.0, 0.7, 0.6).as_rgba_f32()
+                    })
+                    .collect();
+                mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+            }
+        }
+    }
+}
+
+fn lerp(a: Color, b: Color, t: f32) -> [f32; 4] {
+    let (a, b) = (a.as_rgba_f32(), b.as_rgba_f32());
+    let t = t.clamp(0.0, 1.0);
+    [
+        a[0] + (b[0] - a[0]) * t,
+        a[1] + (b[1] - a[1]) * t,
+        a[2] + (b[2] - a[2]) * t,
+        a[3] + (b[3] - a[3]) * t,
+    ]
+}
+
+/// A number in 0..1 that jumps around as `i` counts up, the same every time for the same seed.
+fn hash(i: u32, seed: u32) -> f32 {
+    let mut x = i.wrapping_mul(0x9E37_79B9) ^ seed.wrapping_mul(0x85EB_CA6B);
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x7FEB_352D);
+    x ^= x >> 15;
+    x = x.wrapping_mul(0x846C_A68B);
+    x ^= x >> 16;
+    x as f32 / u32::MAX as f32
+}